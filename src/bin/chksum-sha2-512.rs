@@ -0,0 +1,306 @@
+//! A command-line utility to print or check SHA-2 512 digests, compatible with the GNU coreutils `sha512sum` utility.
+//!
+//! The utility exits with status `0` on success and `1` if any input cannot be read, any digest does not match or the arguments are invalid.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use chksum_sha2_512::manifest::{self, Manifest, Status, VerifyOptions};
+use chksum_sha2_512::{chksum, Digest, Error, SHA2_512};
+use clap::Parser;
+
+const NAME: &str = "chksum-sha2-512";
+const STDIN: &str = "-";
+
+/// Print or check SHA-2 512 checksums.
+///
+/// With no FILE, or when FILE is -, read standard input. Directories are hashed as a whole, unless the recursive mode is enabled.
+#[derive(Debug, Parser)]
+#[command(name = NAME, version)]
+struct Args {
+    /// Files to process.
+    #[arg(value_name = "FILE")]
+    files: Vec<PathBuf>,
+    /// Read in binary mode.
+    #[arg(short, long, overrides_with = "text")]
+    binary: bool,
+    /// Read in text mode (default).
+    #[arg(short, long, overrides_with = "binary")]
+    text: bool,
+    /// Read checksums from the FILEs and check them.
+    #[arg(short, long)]
+    check: bool,
+    /// Create a BSD-style checksum.
+    #[arg(long, conflicts_with = "check")]
+    tag: bool,
+    /// End each output line with NUL, not newline, and disable file name escaping.
+    #[arg(short, long, conflicts_with = "check")]
+    zero: bool,
+    /// Print digests in uppercase.
+    #[arg(long, conflicts_with = "check")]
+    uppercase: bool,
+    /// Print a digest of every file found in directories instead of a digest of the whole directory.
+    #[arg(short, long, conflicts_with = "check")]
+    recursive: bool,
+    /// Don't fail or report status for missing files.
+    #[arg(long, requires = "check")]
+    ignore_missing: bool,
+    /// Don't print OK for each successfully verified file.
+    #[arg(long, requires = "check")]
+    quiet: bool,
+    /// Don't output anything, status code shows success.
+    #[arg(long, requires = "check")]
+    status: bool,
+    /// Exit non-zero for improperly formatted checksum lines.
+    #[arg(long, requires = "check")]
+    strict: bool,
+    /// Warn about improperly formatted checksum lines.
+    #[arg(short, long, requires = "check")]
+    warn: bool,
+}
+
+impl Args {
+    fn files(&self) -> Vec<PathBuf> {
+        if self.files.is_empty() {
+            vec![PathBuf::from(STDIN)]
+        } else {
+            self.files.clone()
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::try_parse() {
+        Ok(args) => args,
+        Err(error) => {
+            // Usage errors exit with 1 like coreutils, help and version with 0.
+            let _ = error.print();
+            return if error.use_stderr() {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            };
+        },
+    };
+    let result = if args.check { check(&args) } else { generate(&args) };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("{NAME}: {error}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+/// Prints the digests of the inputs, returning whether all of them were processed.
+fn generate(args: &Args) -> io::Result<bool> {
+    let mut stdout = io::stdout().lock();
+    let mut success = true;
+    for file in args.files() {
+        if file.as_os_str() == STDIN {
+            let result = chksum_stdin().map_err(Error::from);
+            success &= print_digest(&mut stdout, args, &file, result)?;
+        } else if args.recursive && file.is_dir() {
+            let mut files = Vec::new();
+            success &= walk(&file, &mut files);
+            for file in files {
+                let result = chksum(&file);
+                success &= print_digest(&mut stdout, args, &file, result)?;
+            }
+        } else {
+            let result = chksum(&file);
+            success &= print_digest(&mut stdout, args, &file, result)?;
+        }
+    }
+    stdout.flush()?;
+    Ok(success)
+}
+
+/// Computes the digest of the standard input, reading until the end of input even from a terminal like coreutils does.
+fn chksum_stdin() -> io::Result<Digest> {
+    let mut stdin = io::stdin().lock();
+    let mut hash = SHA2_512::new();
+    loop {
+        let buffer = stdin.fill_buf()?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hash.update(buffer);
+        stdin.consume(length);
+    }
+    Ok(hash.digest())
+}
+
+/// Prints the digest line or reports the error, returning whether the digest was computed.
+fn print_digest(stdout: &mut impl Write, args: &Args, path: &Path, result: Result<Digest, Error>) -> io::Result<bool> {
+    let digest = match result {
+        Ok(digest) => digest,
+        Err(error) => {
+            stdout.flush()?;
+            eprintln!("{NAME}: {}: {}", path.display(), describe(&error));
+            return Ok(false);
+        },
+    };
+    let digest = if args.uppercase {
+        digest.to_hex_uppercase()
+    } else {
+        digest.to_hex_lowercase()
+    };
+    let mut name = path.as_os_str().as_encoded_bytes().to_vec();
+    if !args.zero && manifest::needs_escaping(&name) {
+        stdout.write_all(b"\\")?;
+        name = manifest::escape(&name);
+    }
+    if args.tag {
+        stdout.write_all(b"SHA512 (")?;
+        stdout.write_all(&name)?;
+        stdout.write_all(b") = ")?;
+        stdout.write_all(digest.as_bytes())?;
+    } else {
+        let mode = if args.binary { b'*' } else { b' ' };
+        stdout.write_all(digest.as_bytes())?;
+        stdout.write_all(&[b' ', mode])?;
+        stdout.write_all(&name)?;
+    }
+    stdout.write_all(if args.zero { b"\0" } else { b"\n" })?;
+    Ok(true)
+}
+
+/// Collects the files of the directory tree sorted by path, returning whether all directories were read.
+///
+/// Symbolic links to directories are skipped, so the traversal cannot run into a cycle.
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> bool {
+    let entries = fs::read_dir(dir).and_then(|entries| {
+        entries
+            .map(|entry| entry.and_then(|entry| Ok((entry.path(), entry.file_type()?))))
+            .collect::<io::Result<Vec<_>>>()
+    });
+    let mut entries = match entries {
+        Ok(entries) => entries,
+        Err(error) => {
+            eprintln!("{NAME}: {}: {}", dir.display(), describe(&error));
+            return false;
+        },
+    };
+    entries.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+    let mut success = true;
+    for (entry, file_type) in entries {
+        if file_type.is_dir() {
+            success &= walk(&entry, files);
+        } else if !(file_type.is_symlink() && entry.is_dir()) {
+            files.push(entry);
+        }
+    }
+    success
+}
+
+/// Checks the manifests, returning whether all of them were verified successfully.
+fn check(args: &Args) -> io::Result<bool> {
+    let mut stdout = io::stdout().lock();
+    let mut success = true;
+    for file in args.files() {
+        let manifest = if file.as_os_str() == STDIN {
+            Manifest::read_lenient(io::stdin().lock())
+        } else {
+            File::open(&file).map(BufReader::new).and_then(Manifest::read_lenient)
+        };
+        let manifest = match manifest {
+            Ok(manifest) => manifest,
+            Err(error) => {
+                eprintln!("{NAME}: {}: {}", file.display(), describe(&error));
+                success = false;
+                continue;
+            },
+        };
+        if args.warn {
+            for line in manifest.malformed_lines() {
+                eprintln!(
+                    "{NAME}: {}: {line}: improperly formatted SHA512 checksum line",
+                    file.display()
+                );
+            }
+        }
+        if manifest.is_empty() {
+            eprintln!(
+                "{NAME}: {}: no properly formatted SHA512 checksum lines found",
+                file.display()
+            );
+            success = false;
+            continue;
+        }
+
+        let options = VerifyOptions::new()
+            .with_ignore_missing(args.ignore_missing)
+            .with_strict(args.strict);
+        let report = manifest.verify(&options);
+        if !args.status {
+            for entry in report.entries() {
+                let message: &[u8] = match entry.status() {
+                    Status::Ok if args.quiet => continue,
+                    Status::Ok => b"OK",
+                    Status::Failed => b"FAILED",
+                    Status::Missing if args.ignore_missing => continue,
+                    Status::Missing | Status::Unreadable { .. } => b"FAILED open or read",
+                };
+                if let Some(error) = entry.error() {
+                    stdout.flush()?;
+                    eprintln!("{NAME}: {}: {}", entry.path().display(), describe(&error));
+                }
+                // Unlike digest lines, status lines are escaped only for line breaks.
+                let mut name = entry.path().as_os_str().as_encoded_bytes().to_vec();
+                if name.iter().any(|byte| matches!(byte, b'\n' | b'\r')) {
+                    stdout.write_all(b"\\")?;
+                    name = manifest::escape(&name);
+                }
+                stdout.write_all(&name)?;
+                stdout.write_all(b": ")?;
+                stdout.write_all(message)?;
+                stdout.write_all(b"\n")?;
+            }
+            stdout.flush()?;
+
+            let unread = report.unreadable() + if args.ignore_missing { 0 } else { report.missing() };
+            warn(
+                report.malformed(),
+                "line is improperly formatted",
+                "lines are improperly formatted",
+            );
+            warn(
+                unread,
+                "listed file could not be read",
+                "listed files could not be read",
+            );
+            warn(
+                report.failed(),
+                "computed checksum did NOT match",
+                "computed checksums did NOT match",
+            );
+            if args.ignore_missing && report.ok() == 0 && report.failed() == 0 && report.unreadable() == 0 {
+                eprintln!("{NAME}: {}: no file was verified", file.display());
+            }
+        }
+        success &= report.is_success();
+    }
+    Ok(success)
+}
+
+/// Formats the error without the OS error code suffix, like coreutils does.
+fn describe(error: &impl ToString) -> String {
+    let mut message = error.to_string();
+    if let Some(index) = message.find(" (os error ") {
+        message.truncate(index);
+    }
+    message
+}
+
+fn warn(count: usize, singular: &str, plural: &str) {
+    match count {
+        0 => {},
+        1 => eprintln!("{NAME}: WARNING: 1 {singular}"),
+        count => eprintln!("{NAME}: WARNING: {count} {plural}"),
+    }
+}
@@ -0,0 +1,353 @@
+//! This module provides hashing of fixed-size blocks along with the whole input in a single pass.
+//!
+//! The [`BlockHasher`] computes a digest of every block of the configured length and a digest of the whole input at the same time, so the input is read only once. The last block may be shorter than the block length, an empty input has no blocks.
+//!
+//! The resulting [`BlockDigests`] can be serialized with [`BlockDigests::to_bytes`] and restored with [`BlockDigests::from_bytes`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//! use std::num::NonZeroUsize;
+//!
+//! use chksum_sha2_512 as sha2_512;
+//! use sha2_512::block;
+//!
+//! # fn wrapper(path: &Path) -> std::io::Result<()> {
+//! let block_length = NonZeroUsize::new(4 * 1024 * 1024).unwrap();
+//! let digests = block::chksum(File::open(path)?, block_length)?;
+//! for (index, digest) in digests.blocks().iter().enumerate() {
+//!     println!("{index}: {digest}");
+//! }
+//! println!("{}", digests.digest());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::num::NonZeroUsize;
+use std::result;
+
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::{AsyncBufReadExt as _, AsyncRead};
+
+use crate::{hash, Digest, SHA2_512};
+
+/// Magic bytes identifying serialized [`BlockDigests`].
+const MAGIC: &[u8; 8] = b"SHA2-BLK";
+const VERSION: u8 = 1;
+const HEADER_LENGTH_BYTES: usize = MAGIC.len() + 1 + 8 + 8 + hash::DIGEST_LENGTH_BYTES;
+
+/// Computes the block digests and the whole digest of the reader contents.
+///
+/// # Errors
+///
+/// Returns the I/O error if the reader cannot be read.
+///
+/// # Example
+///
+/// ```rust
+/// use std::num::NonZeroUsize;
+///
+/// use chksum_sha2_512 as sha2_512;
+/// use sha2_512::block;
+///
+/// # fn wrapper() -> std::io::Result<()> {
+/// let data = b"example data";
+/// let digests = block::chksum(&data[..], NonZeroUsize::new(7).unwrap())?;
+/// assert_eq!(
+///     digests.blocks(),
+///     [sha2_512::hash(b"example"), sha2_512::hash(b" data")]
+/// );
+/// assert_eq!(digests.digest(), &sha2_512::hash(data));
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum(reader: impl Read, block_length: NonZeroUsize) -> io::Result<BlockDigests> {
+    let mut hasher = BlockHasher::new(block_length);
+    let mut reader = BufReader::new(reader);
+    loop {
+        let buffer = reader.fill_buf()?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hasher.update(buffer);
+        reader.consume(length);
+    }
+    Ok(hasher.finalize())
+}
+
+/// Computes the block digests and the whole digest of the reader contents.
+///
+/// # Errors
+///
+/// Returns the I/O error if the reader cannot be read.
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum(reader: impl AsyncRead + Unpin, block_length: NonZeroUsize) -> io::Result<BlockDigests> {
+    let mut hasher = BlockHasher::new(block_length);
+    let mut reader = tokio::io::BufReader::new(reader);
+    loop {
+        let buffer = reader.fill_buf().await?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hasher.update(buffer);
+        reader.consume(length);
+    }
+    Ok(hasher.finalize())
+}
+
+/// A hasher computing the digests of fixed-size blocks and of the whole input.
+///
+/// The hasher implements [`Write`], so it can be used as the destination of [`io::copy`], for example from a `Reader`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::num::NonZeroUsize;
+///
+/// use chksum_sha2_512 as sha2_512;
+/// use sha2_512::BlockHasher;
+///
+/// let mut hasher = BlockHasher::new(NonZeroUsize::new(4).unwrap());
+/// hasher.update(b"example");
+/// hasher.update(b" data");
+/// let digests = hasher.finalize();
+/// assert_eq!(digests.blocks().len(), 3);
+/// assert_eq!(digests.length(), 12);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BlockHasher {
+    block_length: NonZeroUsize,
+    whole: SHA2_512,
+    block: SHA2_512,
+    filled: usize,
+    length: u64,
+    blocks: Vec<Digest>,
+}
+
+impl BlockHasher {
+    /// Creates a new hasher with the given block length.
+    #[must_use]
+    pub fn new(block_length: NonZeroUsize) -> Self {
+        Self {
+            block_length,
+            whole: SHA2_512::new(),
+            block: SHA2_512::new(),
+            filled: 0,
+            length: 0,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Updates the hasher with the input data.
+    pub fn update<T>(&mut self, data: T)
+    where
+        T: AsRef<[u8]>,
+    {
+        let mut data = data.as_ref();
+        self.whole.update(data);
+        self.length += data.len() as u64;
+        while !data.is_empty() {
+            let length = data.len().min(self.block_length.get() - self.filled);
+            let (head, tail) = data.split_at(length);
+            self.block.update(head);
+            self.filled += length;
+            if self.filled == self.block_length.get() {
+                self.finish_block();
+            }
+            data = tail;
+        }
+    }
+
+    /// Produces the block digests and the whole digest, including the trailing partial block.
+    #[must_use]
+    pub fn finalize(mut self) -> BlockDigests {
+        if self.filled > 0 {
+            self.finish_block();
+        }
+        BlockDigests {
+            block_length: self.block_length.get() as u64,
+            length: self.length,
+            blocks: self.blocks,
+            digest: self.whole.digest(),
+        }
+    }
+
+    fn finish_block(&mut self) {
+        self.blocks.push(self.block.digest());
+        self.block.reset();
+        self.filled = 0;
+    }
+}
+
+impl Write for BlockHasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The digests of fixed-size blocks and of the whole input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlockDigests {
+    block_length: u64,
+    length: u64,
+    blocks: Vec<Digest>,
+    digest: Digest,
+}
+
+impl BlockDigests {
+    /// Returns the block length.
+    #[must_use]
+    pub const fn block_length(&self) -> u64 {
+        self.block_length
+    }
+
+    /// Returns the length of the whole input.
+    #[must_use]
+    pub const fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// Returns the digests of the blocks in the input order.
+    #[must_use]
+    pub fn blocks(&self) -> &[Digest] {
+        &self.blocks
+    }
+
+    /// Returns the digest of the whole input.
+    #[must_use]
+    pub const fn digest(&self) -> &Digest {
+        &self.digest
+    }
+
+    /// Serializes the digests.
+    ///
+    /// The result is a byte blob with the following layout, all integers are big-endian:
+    ///
+    /// | Offset | Length   | Content                                      |
+    /// |--------|----------|----------------------------------------------|
+    /// | 0      | 8        | magic bytes `SHA2-BLK`                       |
+    /// | 8      | 1        | format version, currently `1`                |
+    /// | 9      | 8        | block length                                 |
+    /// | 17     | 8        | length of the whole input                    |
+    /// | 25     | 64       | digest of the whole input                    |
+    /// | 89     | 64 × *n* | digests of the blocks                        |
+    ///
+    /// The number of blocks *n* is the length of the whole input divided by the block length, rounded up.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chksum_sha2_512::{block, BlockDigests};
+    ///
+    /// # fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let digests = block::chksum(&b"example data"[..], NonZeroUsize::new(4).unwrap())?;
+    /// let bytes = digests.to_bytes();
+    /// assert_eq!(BlockDigests::from_bytes(&bytes)?, digests);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LENGTH_BYTES + self.blocks.len() * hash::DIGEST_LENGTH_BYTES);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.block_length.to_be_bytes());
+        bytes.extend_from_slice(&self.length.to_be_bytes());
+        bytes.extend_from_slice(self.digest.as_bytes());
+        for digest in &self.blocks {
+            bytes.extend_from_slice(digest.as_bytes());
+        }
+        bytes
+    }
+
+    /// Deserializes digests previously produced by [`BlockDigests::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockError`] if the bytes are truncated, were produced by a different format version or are internally inconsistent.
+    pub fn from_bytes(bytes: &[u8]) -> result::Result<Self, BlockError> {
+        if bytes.len() < HEADER_LENGTH_BYTES {
+            let error = BlockError::Truncated {
+                value: bytes.len(),
+                minimum: HEADER_LENGTH_BYTES,
+            };
+            return Err(error);
+        }
+        let (header, bytes) = bytes.split_at(MAGIC.len());
+        if header != MAGIC {
+            return Err(BlockError::InvalidMagic);
+        }
+        let (version, bytes) = bytes.split_at(1);
+        if version[0] != VERSION {
+            return Err(BlockError::UnsupportedVersion { version: version[0] });
+        }
+        let (block_length, bytes) = bytes.split_at(8);
+        let block_length = u64::from_be_bytes(
+            block_length
+                .try_into()
+                .expect("chunk length must be exact size as length"),
+        );
+        if block_length == 0 {
+            return Err(BlockError::InvalidBlockLength);
+        }
+        let (length, bytes) = bytes.split_at(8);
+        let length = u64::from_be_bytes(length.try_into().expect("chunk length must be exact size as length"));
+        let (digest, bytes) = bytes.split_at(hash::DIGEST_LENGTH_BYTES);
+        let digest = digest_from(digest);
+        let count = length.div_ceil(block_length);
+        let proper = usize::try_from(count)
+            .ok()
+            .and_then(|count| count.checked_mul(hash::DIGEST_LENGTH_BYTES));
+        if proper != Some(bytes.len()) {
+            let error = BlockError::InvalidLength {
+                value: bytes.len(),
+                proper: proper.unwrap_or(usize::MAX),
+            };
+            return Err(error);
+        }
+        let blocks = bytes.chunks_exact(hash::DIGEST_LENGTH_BYTES).map(digest_from).collect();
+        Ok(Self {
+            block_length,
+            length,
+            blocks,
+            digest,
+        })
+    }
+}
+
+/// An error type for the block digests deserialization.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum BlockError {
+    /// The bytes are shorter than the fixed-size header.
+    #[error("truncated block digests of length `{value}`, minimum length `{minimum}`")]
+    Truncated { value: usize, minimum: usize },
+    /// The bytes do not start with the expected magic bytes.
+    #[error("invalid block digests magic bytes")]
+    InvalidMagic,
+    /// The format version is not supported.
+    #[error("unsupported block digests version `{version}`")]
+    UnsupportedVersion { version: u8 },
+    /// The block length is zero.
+    #[error("invalid block length `0`")]
+    InvalidBlockLength,
+    /// The length of the block digests differs from the one implied by the input and block lengths.
+    #[error("invalid length `{value}`, proper value `{proper}`")]
+    InvalidLength { value: usize, proper: usize },
+}
+
+fn digest_from(bytes: &[u8]) -> Digest {
+    let bytes: [u8; hash::DIGEST_LENGTH_BYTES] = bytes.try_into().expect("chunk length must be exact size as digest");
+    Digest::from(bytes)
+}
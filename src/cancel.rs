@@ -0,0 +1,189 @@
+//! This module provides cooperative cancellation of file and directory hashing.
+//!
+//! A [`CancellationToken`] is shared between the code performing the hashing and the code requesting the cancellation. The token is checked before every file and between blocks of file contents, and the hashing stops with a dedicated error as soon as the token is cancelled:
+//!
+//! * [`CancelError::Cancelled`] for [`chksum`] and [`async_chksum`],
+//! * [`DirError::Cancelled`](crate::DirError::Cancelled) for [`chksum_dir_with_context`](crate::chksum_dir_with_context) and [`async_chksum_dir_with_context`](crate::async_chksum_dir_with_context) through the [`DirContext`](crate::DirContext).
+//!
+//! # Cancel Safety
+//!
+//! The asynchronous functions keep at most one file open at a time, owned by the returned future. Dropping the future mid-directory closes that file, once any blocking read in flight on the runtime thread pool has completed, so no files are left open.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::thread;
+//!
+//! use chksum_sha2_512 as sha2_512;
+//! use sha2_512::cancel::{self, CancelError, CancellationToken};
+//!
+//! # fn wrapper(path: &Path) -> Result<(), CancelError> {
+//! let token = CancellationToken::new();
+//! let handle = {
+//!     let token = token.clone();
+//!     let path = path.to_path_buf();
+//!     thread::spawn(move || cancel::chksum(path, &token))
+//! };
+//! token.cancel();
+//! match handle.join().unwrap() {
+//!     Ok(digest) => println!("{digest}"),
+//!     Err(CancelError::Cancelled) => println!("cancelled"),
+//!     Err(error) => return Err(error),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::AsyncBufReadExt as _;
+
+use crate::{Digest, SHA2_512};
+
+/// A token for cooperative cancellation of hashing.
+///
+/// Clones of the token share the cancellation state, so cancelling any of them cancels all of them.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new token which is not cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the cancellation of all hashing using the token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the cancellation was requested.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Computes the hash of the file or directory, checking the token before every file and block.
+///
+/// The digest is identical to the one computed by [`chksum`](crate::chksum) for the same path, where directories are hashed as the concatenation of their files in the sorted order.
+///
+/// # Errors
+///
+/// Returns [`CancelError::Cancelled`] if the token was cancelled and [`CancelError::Io`] if any file or directory cannot be read.
+pub fn chksum(path: impl AsRef<Path>, token: &CancellationToken) -> result::Result<Digest, CancelError> {
+    let mut hash = SHA2_512::new();
+    let mut pending = vec![path.as_ref().to_path_buf()];
+    while let Some(path) = pending.pop() {
+        check(token)?;
+        if fs::metadata(&path)?.is_dir() {
+            let entries = fs::read_dir(&path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<_>>>()?;
+            push_sorted(&mut pending, entries);
+        } else {
+            chksum_file(&mut hash, &path, token)?;
+        }
+    }
+    Ok(hash.digest())
+}
+
+/// Computes the hash of the file or directory, checking the token before every file and block.
+///
+/// The digest is identical to the one computed by [`async_chksum`](crate::async_chksum) for the same path. See the [module documentation](self#cancel-safety) for the behavior when the future is dropped.
+///
+/// # Errors
+///
+/// Returns [`CancelError::Cancelled`] if the token was cancelled and [`CancelError::Io`] if any file or directory cannot be read.
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum(path: impl AsRef<Path>, token: &CancellationToken) -> result::Result<Digest, CancelError> {
+    let mut hash = SHA2_512::new();
+    let mut pending = vec![path.as_ref().to_path_buf()];
+    while let Some(path) = pending.pop() {
+        check(token)?;
+        if tokio::fs::metadata(&path).await?.is_dir() {
+            let mut dir = tokio::fs::read_dir(&path).await?;
+            let mut entries = Vec::new();
+            while let Some(entry) = dir.next_entry().await? {
+                entries.push(entry.path());
+            }
+            push_sorted(&mut pending, entries);
+        } else {
+            async_chksum_file(&mut hash, &path, token).await?;
+        }
+    }
+    Ok(hash.digest())
+}
+
+/// An error type for the cancellable hashing.
+#[derive(Debug, thiserror::Error)]
+pub enum CancelError {
+    /// The hashing was cancelled.
+    #[error("hashing cancelled")]
+    Cancelled,
+    /// The I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Returns [`CancelError::Cancelled`] if the token was cancelled.
+fn check(token: &CancellationToken) -> result::Result<(), CancelError> {
+    if token.is_cancelled() {
+        Err(CancelError::Cancelled)
+    } else {
+        Ok(())
+    }
+}
+
+/// Pushes the directory entries on the stack, so they are popped in the sorted order.
+fn push_sorted(pending: &mut Vec<PathBuf>, mut entries: Vec<PathBuf>) {
+    entries.sort_unstable_by(|left, right| right.cmp(left));
+    pending.extend(entries);
+}
+
+/// Updates the hash with the file contents, checking the token between blocks.
+fn chksum_file(hash: &mut SHA2_512, path: &Path, token: &CancellationToken) -> result::Result<(), CancelError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    loop {
+        check(token)?;
+        let buffer = reader.fill_buf()?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hash.update(buffer);
+        reader.consume(length);
+    }
+    Ok(())
+}
+
+/// Updates the hash with the file contents, checking the token between blocks.
+#[cfg(feature = "async-runtime-tokio")]
+async fn async_chksum_file(
+    hash: &mut SHA2_512,
+    path: &Path,
+    token: &CancellationToken,
+) -> result::Result<(), CancelError> {
+    let mut reader = tokio::io::BufReader::new(tokio::fs::File::open(path).await?);
+    loop {
+        check(token)?;
+        let buffer = reader.fill_buf().await?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hash.update(buffer);
+        reader.consume(length);
+    }
+    Ok(())
+}
@@ -0,0 +1,1222 @@
+//! This module provides configurable hashing of directory trees.
+//!
+//! Unlike [`chksum`](crate::chksum), which concatenates the contents of all files found in a directory, [`chksum_dir`] encodes every entry as a separate record, so the digest can depend on file names, sizes, modes and empty directories as configured with [`DirOptions`].
+//!
+//! # Canonical Encoding
+//!
+//! The digest is a SHA-2 512 digest of the following byte stream, all integers are big-endian:
+//!
+//! 1. The header: ASCII `chksum-dir`, the format version byte `1` and the flags byte, where bit `0` marks included paths, bit `1` sizes, bit `2` modes and bit `3` empty directories.
+//! 2. One record per entry, sorted by the byte-wise order of the encoded relative paths.
+//!
+//! A relative path is encoded as its components joined with `/`. On Unix the components are raw bytes, on other platforms they are encoded as (WTF-)8.
+//!
+//! Each record consists of:
+//!
+//! | Field  | Length   | Present                                                                      |
+//! |--------|----------|------------------------------------------------------------------------------|
+//! | kind   | 1        | always, `F` for files, `D` for empty directories and `L` for symbolic links  |
+//! | path   | 8 + *n*  | if paths are included, the length of the encoded path followed by the path |
+//! | size   | 8        | for files, if sizes are included                                             |
+//! | mode   | 4        | for files and empty directories, if modes are included (see below)           |
+//! | digest | 64       | for files, the SHA-2 512 digest of the file contents                         |
+//! | target | 8 + *n*  | for symbolic links, the length of the encoded target followed by the target  |
+//!
+//! On Unix the mode is `st_mode & 0o7777`. On other platforms it is `0o444` for read-only and `0o644` for writable files, `0o555` and `0o755` for directories respectively.
+//!
+//! Directories are represented only by the files they contain, unless they are empty and empty directories are included.
+//!
+//! # Symbolic Links
+//!
+//! Symbolic links, including the hashed path itself, are handled according to the [`SymlinkPolicy`]:
+//!
+//! * [`Follow`](SymlinkPolicy::Follow) hashes the entry the link points to as if it was found in place of the link. A dangling link results in [`DirError::DanglingSymlink`] and a link pointing to one of its ancestor directories results in [`DirError::SymlinkCycle`].
+//! * [`HashTarget`](SymlinkPolicy::HashTarget) records the link with its target path, which is encoded like a relative path and is not resolved.
+//! * [`Skip`](SymlinkPolicy::Skip) ignores the link.
+//! * [`Error`](SymlinkPolicy::Error) fails with [`DirError::Symlink`].
+//!
+//! # Ignore Rules
+//!
+//! With the `ignore-rules` feature, entries can be filtered with [`IgnoreRules`] using the [`.gitignore` pattern format](https://git-scm.com/docs/gitignore#_pattern_format), with patterns relative to the hashed directory. Ignored entries are skipped entirely, so they affect neither the records nor whether their parent directory is empty.
+//!
+//! An entry is ignored if it matches an exclude pattern. A negated exclude pattern (`!pattern`) keeps the entry regardless of ignore files. Otherwise, if ignore files are respected, the `.gitignore` and `.ignore` files found in the tree are consulted, with the deepest matching file taking precedence and `.ignore` taking precedence over `.gitignore` in the same directory. If any include patterns are given, only files matching at least one of them, directly or through one of their parent directories, are hashed. Directories are always traversed, a directory whose files are all filtered out counts as empty.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[cfg(feature = "ignore-rules")]
+//! # {
+//! # use std::path::Path;
+//! use chksum_sha2_512 as sha2_512;
+//! use sha2_512::{DirOptions, IgnoreRules};
+//!
+//! # fn wrapper(path: &Path) -> Result<(), sha2_512::DirError> {
+//! let options = DirOptions::new()
+//!     .include_sizes(true)
+//!     .include_empty_dirs(true)
+//!     .with_rules(
+//!         IgnoreRules::new()
+//!             .exclude("target/")
+//!             .respect_ignore_files(true),
+//!     );
+//! let digest = sha2_512::chksum_dir(path, &options)?;
+//! # Ok(())
+//! # }
+//! # }
+//! ```
+
+use std::fs::{self, File, Metadata};
+use std::io::{self, BufRead, BufReader};
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+use std::path::{Component, Path, PathBuf};
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(feature = "ignore-rules")]
+use std::sync::Arc;
+#[cfg(feature = "parallel")]
+use std::thread;
+
+#[cfg(feature = "ignore-rules")]
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+#[cfg(feature = "ignore-rules")]
+use ignore::Match;
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::AsyncBufReadExt as _;
+
+use crate::cancel::CancellationToken;
+use crate::progress::ProgressReporter;
+use crate::{Digest, SHA2_512};
+
+const HEADER_MAGIC: &[u8] = b"chksum-dir";
+const HEADER_VERSION: u8 = 1;
+
+const FLAG_PATHS: u8 = 0b0001;
+const FLAG_SIZES: u8 = 0b0010;
+const FLAG_MODES: u8 = 0b0100;
+const FLAG_EMPTY_DIRS: u8 = 0b1000;
+
+const KIND_FILE: u8 = b'F';
+const KIND_EMPTY_DIR: u8 = b'D';
+const KIND_SYMLINK: u8 = b'L';
+
+/// Names of the ignore files in the order of increasing precedence.
+const IGNORE_FILES: [&str; 2] = [".gitignore", ".ignore"];
+
+/// Computes the digest of a directory tree with the given options.
+///
+/// If the path points to a file, it is hashed as a single entry with an empty relative path.
+///
+/// # Errors
+///
+/// Returns [`DirError::Io`] if the tree cannot be traversed or any file cannot be read, [`DirError::InvalidPattern`] if any of the rule patterns is malformed and one of the symbolic link errors as described in the [module documentation](self#symbolic-links).
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha2_512 as sha2_512;
+///
+/// # fn wrapper(path: &Path) -> Result<(), sha2_512::DirError> {
+/// let digest = sha2_512::chksum_dir(path, &sha2_512::DirOptions::new())?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_dir(path: impl AsRef<Path>, options: &DirOptions) -> Result<Digest, DirError> {
+    chksum_dir_with_context(path, options, DirContext::new())
+}
+
+/// Computes the digest of a directory tree with the given options, reporting the progress and checking the cancellation token of the context.
+///
+/// Unless already given, the progress total is set to the sum of the sizes of all hashed files. The reported path is the path of the file currently being hashed. The token is checked before every file and block. The digest is identical to the one computed by [`chksum_dir`].
+///
+/// # Errors
+///
+/// Returns [`DirError::Cancelled`] if the token was cancelled and otherwise the same errors as [`chksum_dir`].
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha2_512 as sha2_512;
+/// use sha2_512::cancel::CancellationToken;
+/// use sha2_512::progress::{Progress, ProgressReporter};
+/// use sha2_512::{DirContext, DirOptions};
+///
+/// # fn wrapper(path: &Path) -> Result<(), sha2_512::DirError> {
+/// let reporter = ProgressReporter::new(|progress: &Progress| {
+///     if let Some(path) = progress.path() {
+///         println!("{}: {} bytes", path.display(), progress.processed());
+///     }
+/// });
+/// let token = CancellationToken::new();
+/// let context = DirContext::new()
+///     .with_progress(reporter)
+///     .with_cancellation(token.clone());
+/// let digest = sha2_512::chksum_dir_with_context(path, &DirOptions::new(), context)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_dir_with_context(
+    path: impl AsRef<Path>,
+    options: &DirOptions,
+    context: DirContext,
+) -> Result<Digest, DirError> {
+    chksum_dir_with(path.as_ref(), options, context.reporter, context.token.as_ref())
+}
+
+fn chksum_dir_with(
+    path: &Path,
+    options: &DirOptions,
+    mut reporter: ProgressReporter,
+    token: Option<&CancellationToken>,
+) -> Result<Digest, DirError> {
+    let entries = collect(path, options)?;
+    reporter.default_total(total_size(&entries));
+    let mut hash = header(options);
+    for entry in entries {
+        let digest = match entry.kind {
+            EntryKind::File => Some(chksum_file(&entry.path, &mut reporter, token)?),
+            EntryKind::EmptyDir | EntryKind::Symlink => None,
+        };
+        record(&mut hash, options, &entry, digest.as_ref());
+    }
+    reporter.set_path(None);
+    reporter.finish();
+    Ok(hash.digest())
+}
+
+/// Computes the digest of a directory tree with the given options.
+///
+/// The digest is identical to the one computed by [`chksum_dir`].
+///
+/// # Errors
+///
+/// Returns [`DirError::Io`] if the tree cannot be traversed or any file cannot be read, [`DirError::InvalidPattern`] if any of the rule patterns is malformed and one of the symbolic link errors as described in the [module documentation](self#symbolic-links).
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_dir(path: impl AsRef<Path>, options: &DirOptions) -> Result<Digest, DirError> {
+    async_chksum_dir_with_context(path, options, DirContext::new()).await
+}
+
+/// Computes the digest of a directory tree with the given options, reporting the progress and checking the cancellation token of the context.
+///
+/// The context is used as by [`chksum_dir_with_context`] and the digest is identical to the one computed by [`chksum_dir`]. Dropping the returned future closes the file being hashed, see the [cancel safety](crate::cancel#cancel-safety) notes.
+///
+/// # Errors
+///
+/// Returns [`DirError::Cancelled`] if the token was cancelled and otherwise the same errors as [`async_chksum_dir`].
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_dir_with_context(
+    path: impl AsRef<Path>,
+    options: &DirOptions,
+    context: DirContext,
+) -> Result<Digest, DirError> {
+    async_chksum_dir_with(path.as_ref(), options, context.reporter, context.token.as_ref()).await
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+async fn async_chksum_dir_with(
+    path: &Path,
+    options: &DirOptions,
+    mut reporter: ProgressReporter,
+    token: Option<&CancellationToken>,
+) -> Result<Digest, DirError> {
+    let entries = async_collect(path, options).await?;
+    reporter.default_total(total_size(&entries));
+    let mut hash = header(options);
+    for entry in entries {
+        let digest = match entry.kind {
+            EntryKind::File => Some(async_chksum_file(&entry.path, &mut reporter, token).await?),
+            EntryKind::EmptyDir | EntryKind::Symlink => None,
+        };
+        record(&mut hash, options, &entry, digest.as_ref());
+    }
+    reporter.set_path(None);
+    reporter.finish();
+    Ok(hash.digest())
+}
+
+/// Computes the digest of a directory tree with the given options, hashing files on multiple threads.
+///
+/// At most `threads` files are hashed concurrently. The per-file digests are combined in the canonical order, so the digest is identical to the one computed by [`chksum_dir`] regardless of the number of threads.
+///
+/// # Errors
+///
+/// Returns the same errors as [`chksum_dir`]. If multiple files cannot be read, the error of the first one in the canonical order is returned.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::num::NonZeroUsize;
+///
+/// use chksum_sha2_512 as sha2_512;
+///
+/// # fn wrapper(path: &Path) -> Result<(), sha2_512::DirError> {
+/// let threads = NonZeroUsize::new(8).unwrap();
+/// let digest = sha2_512::par_chksum_dir(path, &sha2_512::DirOptions::new(), threads)?;
+/// assert_eq!(
+///     digest,
+///     sha2_512::chksum_dir(path, &sha2_512::DirOptions::new())?
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "parallel")]
+pub fn par_chksum_dir(path: impl AsRef<Path>, options: &DirOptions, threads: NonZeroUsize) -> Result<Digest, DirError> {
+    let entries = collect(path.as_ref(), options)?;
+    let digests = par_chksum_files(&entries, threads.get())?;
+    let mut hash = header(options);
+    for (entry, digest) in entries.iter().zip(digests) {
+        record(&mut hash, options, entry, digest.as_ref());
+    }
+    Ok(hash.digest())
+}
+
+/// Options controlling which properties of a directory tree contribute to its digest.
+///
+/// By default only relative paths and file contents are included.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512::DirOptions;
+///
+/// let options = DirOptions::new()
+///     .include_paths(true)
+///     .include_sizes(true)
+///     .include_modes(false)
+///     .include_empty_dirs(true);
+/// assert!(options.paths());
+/// assert!(!options.modes());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DirOptions {
+    paths: bool,
+    sizes: bool,
+    modes: bool,
+    empty_dirs: bool,
+    #[cfg(feature = "ignore-rules")]
+    rules: IgnoreRules,
+    symlinks: SymlinkPolicy,
+}
+
+impl DirOptions {
+    /// Creates default options.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            paths: true,
+            sizes: false,
+            modes: false,
+            empty_dirs: false,
+            #[cfg(feature = "ignore-rules")]
+            rules: IgnoreRules::new(),
+            symlinks: SymlinkPolicy::Follow,
+        }
+    }
+
+    /// Sets whether relative paths of entries are included.
+    #[must_use]
+    pub const fn include_paths(mut self, include: bool) -> Self {
+        self.paths = include;
+        self
+    }
+
+    /// Sets whether file sizes are included.
+    #[must_use]
+    pub const fn include_sizes(mut self, include: bool) -> Self {
+        self.sizes = include;
+        self
+    }
+
+    /// Sets whether permission bits are included.
+    #[must_use]
+    pub const fn include_modes(mut self, include: bool) -> Self {
+        self.modes = include;
+        self
+    }
+
+    /// Sets whether empty directories are included.
+    #[must_use]
+    pub const fn include_empty_dirs(mut self, include: bool) -> Self {
+        self.empty_dirs = include;
+        self
+    }
+
+    /// Sets the rules selecting which entries are hashed.
+    #[cfg(feature = "ignore-rules")]
+    #[must_use]
+    pub fn with_rules(mut self, rules: IgnoreRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Sets how symbolic links are handled.
+    #[must_use]
+    pub const fn with_symlink_policy(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlinks = policy;
+        self
+    }
+
+    /// Returns whether relative paths of entries are included.
+    #[must_use]
+    pub const fn paths(&self) -> bool {
+        self.paths
+    }
+
+    /// Returns whether file sizes are included.
+    #[must_use]
+    pub const fn sizes(&self) -> bool {
+        self.sizes
+    }
+
+    /// Returns whether permission bits are included.
+    #[must_use]
+    pub const fn modes(&self) -> bool {
+        self.modes
+    }
+
+    /// Returns whether empty directories are included.
+    #[must_use]
+    pub const fn empty_dirs(&self) -> bool {
+        self.empty_dirs
+    }
+
+    /// Returns the rules selecting which entries are hashed.
+    #[cfg(feature = "ignore-rules")]
+    #[must_use]
+    pub const fn rules(&self) -> &IgnoreRules {
+        &self.rules
+    }
+
+    /// Returns how symbolic links are handled.
+    #[must_use]
+    pub const fn symlink_policy(&self) -> SymlinkPolicy {
+        self.symlinks
+    }
+
+    const fn flags(&self) -> u8 {
+        let mut flags = 0;
+        if self.paths {
+            flags |= FLAG_PATHS;
+        }
+        if self.sizes {
+            flags |= FLAG_SIZES;
+        }
+        if self.modes {
+            flags |= FLAG_MODES;
+        }
+        if self.empty_dirs {
+            flags |= FLAG_EMPTY_DIRS;
+        }
+        flags
+    }
+}
+
+impl Default for DirOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Policy for handling symbolic links found while hashing a directory tree.
+///
+/// See the [module documentation](self#symbolic-links) for details.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum SymlinkPolicy {
+    /// Hashes the entry the link points to.
+    #[default]
+    Follow,
+    /// Hashes the target path of the link as data.
+    HashTarget,
+    /// Skips the link.
+    Skip,
+    /// Fails on the link.
+    Error,
+}
+
+/// The progress reporter and the cancellation token used while hashing a directory tree.
+///
+/// By default nothing is reported and the hashing cannot be cancelled.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512::cancel::CancellationToken;
+/// use chksum_sha2_512::progress::{Progress, ProgressReporter};
+/// use chksum_sha2_512::DirContext;
+///
+/// let token = CancellationToken::new();
+/// let context = DirContext::new()
+///     .with_progress(ProgressReporter::new(|_: &Progress| {}))
+///     .with_cancellation(token.clone());
+/// assert!(context.cancellation().is_some());
+/// ```
+#[derive(Debug, Default)]
+pub struct DirContext {
+    reporter: ProgressReporter,
+    token: Option<CancellationToken>,
+}
+
+impl DirContext {
+    /// Creates a context without progress reporting and cancellation.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the reporter receiving the progress.
+    #[must_use]
+    pub fn with_progress(mut self, reporter: ProgressReporter) -> Self {
+        self.reporter = reporter;
+        self
+    }
+
+    /// Sets the token checked before every file and block.
+    #[must_use]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Returns the token checked before every file and block, if any.
+    #[must_use]
+    pub const fn cancellation(&self) -> Option<&CancellationToken> {
+        self.token.as_ref()
+    }
+}
+
+/// Rules selecting which entries of a directory tree are hashed.
+///
+/// See the [module documentation](self#ignore-rules) for the matching semantics.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512::IgnoreRules;
+///
+/// let rules = IgnoreRules::new()
+///     .exclude("target/")
+///     .exclude("*.swp")
+///     .include("*.rs")
+///     .respect_ignore_files(true);
+/// assert_eq!(rules.excludes(), ["target/", "*.swp"]);
+/// assert!(rules.ignore_files());
+/// ```
+#[cfg(feature = "ignore-rules")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IgnoreRules {
+    includes: Vec<String>,
+    excludes: Vec<String>,
+    ignore_files: bool,
+}
+
+#[cfg(feature = "ignore-rules")]
+impl IgnoreRules {
+    /// Creates rules that do not ignore anything.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            includes: Vec::new(),
+            excludes: Vec::new(),
+            ignore_files: false,
+        }
+    }
+
+    /// Adds a pattern that files must match to be hashed.
+    #[must_use]
+    pub fn include(mut self, pattern: impl Into<String>) -> Self {
+        self.includes.push(pattern.into());
+        self
+    }
+
+    /// Adds a pattern of entries that are skipped.
+    #[must_use]
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.excludes.push(pattern.into());
+        self
+    }
+
+    /// Sets whether `.gitignore` and `.ignore` files found in the tree are respected.
+    #[must_use]
+    pub const fn respect_ignore_files(mut self, respect: bool) -> Self {
+        self.ignore_files = respect;
+        self
+    }
+
+    /// Returns the include patterns.
+    #[must_use]
+    pub fn includes(&self) -> &[String] {
+        &self.includes
+    }
+
+    /// Returns the exclude patterns.
+    #[must_use]
+    pub fn excludes(&self) -> &[String] {
+        &self.excludes
+    }
+
+    /// Returns whether ignore files found in the tree are respected.
+    #[must_use]
+    pub const fn ignore_files(&self) -> bool {
+        self.ignore_files
+    }
+}
+
+/// An error type for the directory hashing.
+#[derive(Debug, thiserror::Error)]
+pub enum DirError {
+    /// The I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The include or exclude pattern is malformed, only returned with the `ignore-rules` feature.
+    #[error("invalid pattern `{pattern}`: {reason}")]
+    InvalidPattern { pattern: String, reason: String },
+    /// The symbolic link was found while the policy is [`SymlinkPolicy::Error`].
+    #[error("symbolic link `{path}`")]
+    Symlink { path: PathBuf },
+    /// The followed symbolic link points to a nonexistent entry.
+    #[error("dangling symbolic link `{path}`")]
+    DanglingSymlink { path: PathBuf },
+    /// The followed symbolic link points to one of its ancestor directories.
+    #[error("symbolic link cycle at `{path}`")]
+    SymlinkCycle { path: PathBuf },
+    /// The hashing was cancelled with the [`CancellationToken`].
+    #[error("hashing cancelled")]
+    Cancelled,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EntryKind {
+    File,
+    EmptyDir,
+    Symlink,
+}
+
+/// A single entry of a directory tree.
+#[derive(Debug)]
+struct Entry {
+    kind: EntryKind,
+    /// The absolute path used for reading.
+    path: PathBuf,
+    /// The encoded relative path used for sorting and hashing.
+    key: Vec<u8>,
+    size: u64,
+    mode: u32,
+    /// The encoded target of a symbolic link.
+    target: Vec<u8>,
+}
+
+impl Entry {
+    fn new(kind: EntryKind, path: PathBuf, key: Vec<u8>, metadata: &Metadata) -> Self {
+        Self {
+            kind,
+            path,
+            key,
+            size: metadata.len(),
+            mode: mode(metadata),
+            target: Vec::new(),
+        }
+    }
+
+    fn symlink(path: PathBuf, key: Vec<u8>, target: &Path) -> Self {
+        Self {
+            kind: EntryKind::Symlink,
+            path,
+            key,
+            size: 0,
+            mode: 0,
+            target: encode(target),
+        }
+    }
+}
+
+/// A path after applying the symbolic link policy.
+enum Resolved {
+    /// The path is not a link.
+    Entry(Metadata),
+    /// The path is a followed link.
+    Followed(Metadata),
+    /// The path is a link hashed as its target.
+    Symlink(PathBuf),
+}
+
+/// A directory waiting to be traversed.
+struct Pending {
+    path: PathBuf,
+    key: Vec<u8>,
+    metadata: Metadata,
+    /// The ignore files of the directory ancestors ordered from the root.
+    layers: Vec<Layer>,
+    /// The canonical paths of the directory and its ancestors, used for cycle detection.
+    ancestors: Vec<PathBuf>,
+}
+
+impl Pending {
+    fn child(&self, path: PathBuf, key: Vec<u8>, metadata: Metadata, canonical: PathBuf) -> Self {
+        let mut ancestors = self.ancestors.clone();
+        ancestors.push(canonical);
+        Self {
+            path,
+            key,
+            metadata,
+            layers: self.layers.clone(),
+            ancestors,
+        }
+    }
+}
+
+/// Traverses the tree and returns its entries in the canonical order.
+fn collect(root: &Path, options: &DirOptions) -> Result<Vec<Entry>, DirError> {
+    let mut entries = Vec::new();
+    let metadata = match resolve(root, options.symlinks)? {
+        None => return Ok(entries),
+        Some(Resolved::Symlink(target)) => {
+            entries.push(Entry::symlink(root.to_path_buf(), Vec::new(), &target));
+            return Ok(entries);
+        },
+        Some(Resolved::Entry(metadata) | Resolved::Followed(metadata)) => metadata,
+    };
+    if !metadata.is_dir() {
+        entries.push(Entry::new(EntryKind::File, root.to_path_buf(), Vec::new(), &metadata));
+        return Ok(entries);
+    }
+
+    let filter = Filter::new(root, options)?;
+    let mut stack = vec![Pending {
+        path: root.to_path_buf(),
+        key: Vec::new(),
+        metadata,
+        layers: Vec::new(),
+        ancestors: vec![fs::canonicalize(root)?],
+    }];
+    while let Some(mut dir) = stack.pop() {
+        if filter.ignore_files {
+            let mut files = Vec::new();
+            for name in IGNORE_FILES {
+                let path = dir.path.join(name);
+                if let Some(contents) = not_found_as_none(fs::read_to_string(&path))? {
+                    files.push((path, contents));
+                }
+            }
+            dir.layers.extend(ignore_layer(&dir.path, files));
+        }
+        let mut empty = true;
+        for dir_entry in fs::read_dir(&dir.path)? {
+            let dir_entry = dir_entry?;
+            let path = dir_entry.path();
+            let (metadata, followed) = match resolve(&path, options.symlinks)? {
+                None => continue,
+                Some(Resolved::Entry(metadata)) => (metadata, false),
+                Some(Resolved::Followed(metadata)) => (metadata, true),
+                Some(Resolved::Symlink(target)) => {
+                    if !filter.is_ignored(&dir.layers, &path, false) && filter.is_included(&path, false) {
+                        empty = false;
+                        let key = child_key(&dir.key, &path);
+                        entries.push(Entry::symlink(path, key, &target));
+                    }
+                    continue;
+                },
+            };
+            if filter.is_ignored(&dir.layers, &path, metadata.is_dir()) {
+                continue;
+            }
+            let key = child_key(&dir.key, &path);
+            if metadata.is_dir() {
+                empty = false;
+                let canonical = if followed {
+                    fs::canonicalize(&path)?
+                } else {
+                    dir.ancestors
+                        .last()
+                        .map_or_else(|| path.clone(), |parent| parent.join(dir_entry.file_name()))
+                };
+                check_cycle(&dir.ancestors, &canonical, &path)?;
+                stack.push(dir.child(path, key, metadata, canonical));
+            } else if filter.is_included(&path, false) {
+                empty = false;
+                entries.push(Entry::new(EntryKind::File, path, key, &metadata));
+            }
+        }
+        if empty && options.empty_dirs {
+            entries.push(Entry::new(EntryKind::EmptyDir, dir.path, dir.key, &dir.metadata));
+        }
+    }
+    entries.sort_by(|lhs, rhs| lhs.key.cmp(&rhs.key));
+    Ok(entries)
+}
+
+/// Traverses the tree and returns its entries in the canonical order.
+#[cfg(feature = "async-runtime-tokio")]
+async fn async_collect(root: &Path, options: &DirOptions) -> Result<Vec<Entry>, DirError> {
+    let mut entries = Vec::new();
+    let metadata = match async_resolve(root, options.symlinks).await? {
+        None => return Ok(entries),
+        Some(Resolved::Symlink(target)) => {
+            entries.push(Entry::symlink(root.to_path_buf(), Vec::new(), &target));
+            return Ok(entries);
+        },
+        Some(Resolved::Entry(metadata) | Resolved::Followed(metadata)) => metadata,
+    };
+    if !metadata.is_dir() {
+        entries.push(Entry::new(EntryKind::File, root.to_path_buf(), Vec::new(), &metadata));
+        return Ok(entries);
+    }
+
+    let filter = Filter::new(root, options)?;
+    let mut stack = vec![Pending {
+        path: root.to_path_buf(),
+        key: Vec::new(),
+        metadata,
+        layers: Vec::new(),
+        ancestors: vec![tokio::fs::canonicalize(root).await?],
+    }];
+    while let Some(mut dir) = stack.pop() {
+        if filter.ignore_files {
+            let mut files = Vec::new();
+            for name in IGNORE_FILES {
+                let path = dir.path.join(name);
+                if let Some(contents) = not_found_as_none(tokio::fs::read_to_string(&path).await)? {
+                    files.push((path, contents));
+                }
+            }
+            dir.layers.extend(ignore_layer(&dir.path, files));
+        }
+        let mut empty = true;
+        let mut read_dir = tokio::fs::read_dir(&dir.path).await?;
+        while let Some(dir_entry) = read_dir.next_entry().await? {
+            let path = dir_entry.path();
+            let (metadata, followed) = match async_resolve(&path, options.symlinks).await? {
+                None => continue,
+                Some(Resolved::Entry(metadata)) => (metadata, false),
+                Some(Resolved::Followed(metadata)) => (metadata, true),
+                Some(Resolved::Symlink(target)) => {
+                    if !filter.is_ignored(&dir.layers, &path, false) && filter.is_included(&path, false) {
+                        empty = false;
+                        let key = child_key(&dir.key, &path);
+                        entries.push(Entry::symlink(path, key, &target));
+                    }
+                    continue;
+                },
+            };
+            if filter.is_ignored(&dir.layers, &path, metadata.is_dir()) {
+                continue;
+            }
+            let key = child_key(&dir.key, &path);
+            if metadata.is_dir() {
+                empty = false;
+                let canonical = if followed {
+                    tokio::fs::canonicalize(&path).await?
+                } else {
+                    dir.ancestors
+                        .last()
+                        .map_or_else(|| path.clone(), |parent| parent.join(dir_entry.file_name()))
+                };
+                check_cycle(&dir.ancestors, &canonical, &path)?;
+                stack.push(dir.child(path, key, metadata, canonical));
+            } else if filter.is_included(&path, false) {
+                empty = false;
+                entries.push(Entry::new(EntryKind::File, path, key, &metadata));
+            }
+        }
+        if empty && options.empty_dirs {
+            entries.push(Entry::new(EntryKind::EmptyDir, dir.path, dir.key, &dir.metadata));
+        }
+    }
+    entries.sort_by(|lhs, rhs| lhs.key.cmp(&rhs.key));
+    Ok(entries)
+}
+
+/// Applies the symbolic link policy to the path, returning `None` if it is skipped.
+fn resolve(path: &Path, policy: SymlinkPolicy) -> Result<Option<Resolved>, DirError> {
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.file_type().is_symlink() {
+        return Ok(Some(Resolved::Entry(metadata)));
+    }
+    match policy {
+        SymlinkPolicy::Follow => {
+            match fs::metadata(path) {
+                Ok(metadata) => Ok(Some(Resolved::Followed(metadata))),
+                Err(error) => Err(dangling(error, path)),
+            }
+        },
+        SymlinkPolicy::HashTarget => Ok(Some(Resolved::Symlink(fs::read_link(path)?))),
+        SymlinkPolicy::Skip => Ok(None),
+        SymlinkPolicy::Error => {
+            Err(DirError::Symlink {
+                path: path.to_path_buf(),
+            })
+        },
+    }
+}
+
+/// Applies the symbolic link policy to the path, returning `None` if it is skipped.
+#[cfg(feature = "async-runtime-tokio")]
+async fn async_resolve(path: &Path, policy: SymlinkPolicy) -> Result<Option<Resolved>, DirError> {
+    let metadata = tokio::fs::symlink_metadata(path).await?;
+    if !metadata.file_type().is_symlink() {
+        return Ok(Some(Resolved::Entry(metadata)));
+    }
+    match policy {
+        SymlinkPolicy::Follow => {
+            match tokio::fs::metadata(path).await {
+                Ok(metadata) => Ok(Some(Resolved::Followed(metadata))),
+                Err(error) => Err(dangling(error, path)),
+            }
+        },
+        SymlinkPolicy::HashTarget => Ok(Some(Resolved::Symlink(tokio::fs::read_link(path).await?))),
+        SymlinkPolicy::Skip => Ok(None),
+        SymlinkPolicy::Error => {
+            Err(DirError::Symlink {
+                path: path.to_path_buf(),
+            })
+        },
+    }
+}
+
+/// Maps the error of following a link, distinguishing a missing target.
+fn dangling(error: io::Error, path: &Path) -> DirError {
+    if error.kind() == io::ErrorKind::NotFound {
+        DirError::DanglingSymlink {
+            path: path.to_path_buf(),
+        }
+    } else {
+        DirError::Io(error)
+    }
+}
+
+/// Fails if the canonical path of a directory is one of its ancestors.
+fn check_cycle(ancestors: &[PathBuf], canonical: &Path, path: &Path) -> Result<(), DirError> {
+    if ancestors.iter().any(|ancestor| ancestor == canonical) {
+        return Err(DirError::SymlinkCycle {
+            path: path.to_path_buf(),
+        });
+    }
+    Ok(())
+}
+
+/// The compiled ignore files of a single directory.
+#[cfg(feature = "ignore-rules")]
+type Layer = Arc<Gitignore>;
+
+/// The ignore files are never read without the `ignore-rules` feature.
+#[cfg(not(feature = "ignore-rules"))]
+type Layer = std::convert::Infallible;
+
+/// The compiled form of [`IgnoreRules`] for a single traversal root.
+#[cfg(feature = "ignore-rules")]
+struct Filter {
+    includes: Option<Gitignore>,
+    excludes: Gitignore,
+    ignore_files: bool,
+}
+
+#[cfg(feature = "ignore-rules")]
+impl Filter {
+    fn new(root: &Path, options: &DirOptions) -> Result<Self, DirError> {
+        let rules = &options.rules;
+        let includes = if rules.includes.is_empty() {
+            None
+        } else {
+            Some(compile(root, &rules.includes)?)
+        };
+        let excludes = compile(root, &rules.excludes)?;
+        let ignore_files = rules.ignore_files;
+        Ok(Self {
+            includes,
+            excludes,
+            ignore_files,
+        })
+    }
+
+    /// Checks whether the entry is ignored, given the ignore files of its ancestors ordered from the root.
+    fn is_ignored(&self, layers: &[Layer], path: &Path, is_dir: bool) -> bool {
+        match self.excludes.matched(path, is_dir) {
+            Match::Ignore(_) => return true,
+            Match::Whitelist(_) => return false,
+            Match::None => {},
+        }
+        layers
+            .iter()
+            .rev()
+            .map(|layer| layer.matched(path, is_dir))
+            .find(|matched| !matched.is_none())
+            .is_some_and(|matched| matched.is_ignore())
+    }
+
+    /// Checks whether the entry or any of its parent directories matches the include patterns, if there are any.
+    fn is_included(&self, path: &Path, is_dir: bool) -> bool {
+        self.includes.as_ref().map_or(true, |includes| {
+            includes.matched_path_or_any_parents(path, is_dir).is_ignore()
+        })
+    }
+}
+
+/// A filter accepting every entry, used without the `ignore-rules` feature.
+#[cfg(not(feature = "ignore-rules"))]
+struct Filter {
+    ignore_files: bool,
+}
+
+#[cfg(not(feature = "ignore-rules"))]
+impl Filter {
+    #[allow(clippy::unnecessary_wraps)]
+    const fn new(_root: &Path, _options: &DirOptions) -> Result<Self, DirError> {
+        Ok(Self { ignore_files: false })
+    }
+
+    const fn is_ignored(&self, _layers: &[Layer], _path: &Path, _is_dir: bool) -> bool {
+        false
+    }
+
+    const fn is_included(&self, _path: &Path, _is_dir: bool) -> bool {
+        true
+    }
+}
+
+/// Compiles the patterns relative to the root.
+#[cfg(feature = "ignore-rules")]
+fn compile(root: &Path, patterns: &[String]) -> Result<Gitignore, DirError> {
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in patterns {
+        builder.add_line(None, pattern).map_err(|error| {
+            DirError::InvalidPattern {
+                pattern: pattern.clone(),
+                reason: error.to_string(),
+            }
+        })?;
+    }
+    builder.build().map_err(|error| {
+        DirError::InvalidPattern {
+            pattern: patterns.join(", "),
+            reason: error.to_string(),
+        }
+    })
+}
+
+/// Compiles the ignore files of the directory, skipping malformed lines like Git does.
+#[cfg(feature = "ignore-rules")]
+fn ignore_layer(dir: &Path, files: Vec<(PathBuf, String)>) -> Option<Arc<Gitignore>> {
+    if files.is_empty() {
+        return None;
+    }
+    let mut builder = GitignoreBuilder::new(dir);
+    for (path, contents) in files {
+        for line in contents.lines() {
+            let _ = builder.add_line(Some(path.clone()), line);
+        }
+    }
+    builder.build().ok().map(Arc::new)
+}
+
+/// Ignore files are never consulted without the `ignore-rules` feature.
+#[cfg(not(feature = "ignore-rules"))]
+#[allow(clippy::needless_pass_by_value)]
+fn ignore_layer(_dir: &Path, _files: Vec<(PathBuf, String)>) -> Option<Layer> {
+    None
+}
+
+/// Treats a missing file as absent rather than as an error.
+fn not_found_as_none<T>(result: io::Result<T>) -> io::Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Encodes the path with its components joined with `/`.
+fn encode(path: &Path) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    for component in path.components() {
+        if !encoded.is_empty() && encoded.last() != Some(&b'/') {
+            encoded.push(b'/');
+        }
+        match component {
+            Component::RootDir => {
+                if encoded.last() != Some(&b'/') {
+                    encoded.push(b'/');
+                }
+            },
+            component => encoded.extend_from_slice(component.as_os_str().as_encoded_bytes()),
+        }
+    }
+    encoded
+}
+
+/// Appends the file name of the path to the encoded parent path.
+fn child_key(parent: &[u8], path: &Path) -> Vec<u8> {
+    let name = path.file_name().unwrap_or_default();
+    let mut key = Vec::with_capacity(parent.len() + 1 + name.len());
+    key.extend_from_slice(parent);
+    if !parent.is_empty() {
+        key.push(b'/');
+    }
+    key.extend_from_slice(name.as_encoded_bytes());
+    key
+}
+
+#[cfg(unix)]
+fn mode(metadata: &Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn mode(metadata: &Metadata) -> u32 {
+    match (metadata.is_dir(), metadata.permissions().readonly()) {
+        (false, true) => 0o444,
+        (false, false) => 0o644,
+        (true, true) => 0o555,
+        (true, false) => 0o755,
+    }
+}
+
+/// Creates a new hash with the encoding header already processed.
+fn header(options: &DirOptions) -> SHA2_512 {
+    let mut hash = SHA2_512::new();
+    hash.update(HEADER_MAGIC);
+    hash.update([HEADER_VERSION, options.flags()]);
+    hash
+}
+
+/// Processes a single entry record.
+fn record(hash: &mut SHA2_512, options: &DirOptions, entry: &Entry, digest: Option<&Digest>) {
+    let kind = match entry.kind {
+        EntryKind::File => KIND_FILE,
+        EntryKind::EmptyDir => KIND_EMPTY_DIR,
+        EntryKind::Symlink => KIND_SYMLINK,
+    };
+    hash.update([kind]);
+    if options.paths {
+        hash.update((entry.key.len() as u64).to_be_bytes());
+        hash.update(&entry.key);
+    }
+    if options.sizes && entry.kind == EntryKind::File {
+        hash.update(entry.size.to_be_bytes());
+    }
+    if options.modes && entry.kind != EntryKind::Symlink {
+        hash.update(entry.mode.to_be_bytes());
+    }
+    if let Some(digest) = digest {
+        hash.update(digest);
+    }
+    if entry.kind == EntryKind::Symlink {
+        hash.update((entry.target.len() as u64).to_be_bytes());
+        hash.update(&entry.target);
+    }
+}
+
+/// Returns [`DirError::Cancelled`] if the token was cancelled.
+fn check(token: Option<&CancellationToken>) -> Result<(), DirError> {
+    match token {
+        Some(token) if token.is_cancelled() => Err(DirError::Cancelled),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the sum of the sizes of the file entries.
+fn total_size(entries: &[Entry]) -> u64 {
+    entries
+        .iter()
+        .filter(|entry| entry.kind == EntryKind::File)
+        .map(|entry| entry.size)
+        .sum()
+}
+
+/// Computes the digest of the file contents, accounting for the processed bytes and checking the token between blocks.
+fn chksum_file(
+    path: &Path,
+    reporter: &mut ProgressReporter,
+    token: Option<&CancellationToken>,
+) -> Result<Digest, DirError> {
+    let mut hash = SHA2_512::new();
+    check(token)?;
+    let mut reader = BufReader::new(File::open(path)?);
+    reporter.set_path(Some(path));
+    loop {
+        check(token)?;
+        let buffer = reader.fill_buf()?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hash.update(buffer);
+        reader.consume(length);
+        reporter.advance(length as u64);
+    }
+    Ok(hash.digest())
+}
+
+/// Computes the digests of the file entries on a pool of worker threads, preserving the order of entries.
+#[cfg(feature = "parallel")]
+fn par_chksum_files(entries: &[Entry], threads: usize) -> Result<Vec<Option<Digest>>, DirError> {
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let worker = || {
+        let mut results = Vec::new();
+        while !failed.load(Ordering::Relaxed) {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(entry) = entries.get(index) else {
+                break;
+            };
+            if entry.kind != EntryKind::File {
+                continue;
+            }
+            let result = chksum_file(&entry.path, &mut ProgressReporter::default(), None);
+            if result.is_err() {
+                failed.store(true, Ordering::Relaxed);
+            }
+            results.push((index, result));
+        }
+        results
+    };
+
+    let mut digests = vec![None; entries.len()];
+    let mut errors = Vec::new();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.min(entries.len())).map(|_| scope.spawn(worker)).collect();
+        for worker in workers {
+            let results = worker
+                .join()
+                .unwrap_or_else(|payload| std::panic::resume_unwind(payload));
+            for (index, result) in results {
+                match result {
+                    Ok(digest) => digests[index] = Some(digest),
+                    Err(error) => errors.push((index, error)),
+                }
+            }
+        }
+    });
+    match errors.into_iter().min_by_key(|(index, _)| *index) {
+        Some((_, error)) => Err(error),
+        None => Ok(digests),
+    }
+}
+
+/// Computes the digest of the file contents, accounting for the processed bytes and checking the token between blocks.
+#[cfg(feature = "async-runtime-tokio")]
+async fn async_chksum_file(
+    path: &Path,
+    reporter: &mut ProgressReporter,
+    token: Option<&CancellationToken>,
+) -> Result<Digest, DirError> {
+    let mut hash = SHA2_512::new();
+    check(token)?;
+    let mut reader = tokio::io::BufReader::new(tokio::fs::File::open(path).await?);
+    reporter.set_path(Some(path));
+    loop {
+        check(token)?;
+        let buffer = reader.fill_buf().await?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hash.update(buffer);
+        reader.consume(length);
+        reporter.advance(length as u64);
+    }
+    Ok(hash.digest())
+}
@@ -0,0 +1,314 @@
+//! The SHA-512 compression engine shared by all hash variants.
+//!
+//! All variants from the SHA-512 family use the same compression function and padding, they differ only in initial hash values and output lengths.
+
+use std::result;
+
+use crate::{hash, StateError};
+
+/// Number of 64-bit words in the chaining state.
+pub(crate) const STATE_LENGTH_QWORDS: usize = 8;
+
+const BLOCK_LENGTH_BYTES: usize = hash::BLOCK_LENGTH_BYTES;
+const BLOCK_LENGTH_QWORDS: usize = BLOCK_LENGTH_BYTES / 8;
+const LENGTH_FIELD_BYTES: usize = 16;
+
+/// Length of the magic prefix of an exported state.
+pub(crate) const STATE_MAGIC_LENGTH_BYTES: usize = 8;
+/// Current version of the exported state format.
+pub(crate) const STATE_VERSION: u8 = 1;
+/// Length of an exported state without the buffered tail.
+const STATE_HEADER_LENGTH_BYTES: usize = STATE_MAGIC_LENGTH_BYTES + 1 + STATE_LENGTH_QWORDS * 8 + 16 + 1;
+
+/// Initial hash value for SHA-512.
+#[allow(clippy::unreadable_literal)]
+#[rustfmt::skip]
+pub(crate) const SHA2_512_IV: [u64; STATE_LENGTH_QWORDS] = [
+    0x6A09E667F3BCC908, 0xBB67AE8584CAA73B, 0x3C6EF372FE94F82B, 0xA54FF53A5F1D36F1,
+    0x510E527FADE682D1, 0x9B05688C2B3E6C1F, 0x1F83D9ABFB41BD6B, 0x5BE0CD19137E2179,
+];
+
+/// Initial hash value for SHA-384.
+#[allow(clippy::unreadable_literal)]
+#[rustfmt::skip]
+pub(crate) const SHA2_384_IV: [u64; STATE_LENGTH_QWORDS] = [
+    0xCBBB9D5DC1059ED8, 0x629A292A367CD507, 0x9159015A3070DD17, 0x152FECD8F70E5939,
+    0x67332667FFC00B31, 0x8EB44A8768581511, 0xDB0C2E0D64F98FA7, 0x47B5481DBEFA4FA4,
+];
+
+/// Initial hash value for SHA-512/224.
+#[allow(clippy::unreadable_literal)]
+#[rustfmt::skip]
+pub(crate) const SHA2_512_224_IV: [u64; STATE_LENGTH_QWORDS] = [
+    0x8C3D37C819544DA2, 0x73E1996689DCD4D6, 0x1DFAB7AE32FF9C82, 0x679DD514582F9FCF,
+    0x0F6D2B697BD44DA8, 0x77E36F7304C48942, 0x3F9D85A86A1D36C8, 0x1112E6AD91D692A1,
+];
+
+/// Initial hash value for SHA-512/256.
+#[allow(clippy::unreadable_literal)]
+#[rustfmt::skip]
+pub(crate) const SHA2_512_256_IV: [u64; STATE_LENGTH_QWORDS] = [
+    0x22312194FC2BF72C, 0x9F555FA3C84C64C2, 0x2393B86B6F53B151, 0x963877195940EABD,
+    0x96283EE2A88EFFE3, 0xBE5E1E2553863992, 0x2B0199FC2C85B8AA, 0x0EB72DDC81C52CA2,
+];
+
+#[allow(clippy::unreadable_literal)]
+#[rustfmt::skip]
+const K: [u64; 80] = [
+    0x428A2F98D728AE22, 0x7137449123EF65CD, 0xB5C0FBCFEC4D3B2F, 0xE9B5DBA58189DBBC,
+    0x3956C25BF348B538, 0x59F111F1B605D019, 0x923F82A4AF194F9B, 0xAB1C5ED5DA6D8118,
+    0xD807AA98A3030242, 0x12835B0145706FBE, 0x243185BE4EE4B28C, 0x550C7DC3D5FFB4E2,
+    0x72BE5D74F27B896F, 0x80DEB1FE3B1696B1, 0x9BDC06A725C71235, 0xC19BF174CF692694,
+    0xE49B69C19EF14AD2, 0xEFBE4786384F25E3, 0x0FC19DC68B8CD5B5, 0x240CA1CC77AC9C65,
+    0x2DE92C6F592B0275, 0x4A7484AA6EA6E483, 0x5CB0A9DCBD41FBD4, 0x76F988DA831153B5,
+    0x983E5152EE66DFAB, 0xA831C66D2DB43210, 0xB00327C898FB213F, 0xBF597FC7BEEF0EE4,
+    0xC6E00BF33DA88FC2, 0xD5A79147930AA725, 0x06CA6351E003826F, 0x142929670A0E6E70,
+    0x27B70A8546D22FFC, 0x2E1B21385C26C926, 0x4D2C6DFC5AC42AED, 0x53380D139D95B3DF,
+    0x650A73548BAF63DE, 0x766A0ABB3C77B2A8, 0x81C2C92E47EDAEE6, 0x92722C851482353B,
+    0xA2BFE8A14CF10364, 0xA81A664BBC423001, 0xC24B8B70D0F89791, 0xC76C51A30654BE30,
+    0xD192E819D6EF5218, 0xD69906245565A910, 0xF40E35855771202A, 0x106AA07032BBD1B8,
+    0x19A4C116B8D2D0C8, 0x1E376C085141AB53, 0x2748774CDF8EEB99, 0x34B0BCB5E19B48A8,
+    0x391C0CB3C5C95A63, 0x4ED8AA4AE3418ACB, 0x5B9CCA4F7763E373, 0x682E6FF3D6B2B8A3,
+    0x748F82EE5DEFB2FC, 0x78A5636F43172F60, 0x84C87814A1F0AB72, 0x8CC702081A6439EC,
+    0x90BEFFFA23631E28, 0xA4506CEBDE82BDE9, 0xBEF9A3F7B2C67915, 0xC67178F2E372532B,
+    0xCA273ECEEA26619C, 0xD186B8C721C0C207, 0xEADA7DD6CDE0EB1E, 0xF57D4F7FEE6ED178,
+    0x06F067AA72176FBA, 0x0A637DC5A2C898A6, 0x113F9804BEF90DAE, 0x1B710B35131C471B,
+    0x28DB77F523047D84, 0x32CAAB7B40C72493, 0x3C9EBE0A15C9BEBC, 0x431D67C49C100D4C,
+    0x4CC5D4BECB3E42B6, 0x597F299CFC657E2A, 0x5FCB6FAB3AD6FAEC, 0x6C44198C4A475817,
+];
+
+/// An in-progress hash computation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Engine {
+    initial: [u64; STATE_LENGTH_QWORDS],
+    state: [u64; STATE_LENGTH_QWORDS],
+    unprocessed: Vec<u8>,
+    processed: u128,
+}
+
+impl Engine {
+    /// Creates a new engine starting from the given initial hash value.
+    pub(crate) fn new(initial: [u64; STATE_LENGTH_QWORDS]) -> Self {
+        let state = initial;
+        let unprocessed = Vec::with_capacity(BLOCK_LENGTH_BYTES);
+        let processed = 0;
+        Self {
+            initial,
+            state,
+            unprocessed,
+            processed,
+        }
+    }
+
+    /// Updates the internal state with an input data.
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        let mut data = data;
+
+        if !self.unprocessed.is_empty() {
+            let missing = BLOCK_LENGTH_BYTES - self.unprocessed.len();
+            if data.len() < missing {
+                // Not enough data even for one block.
+                self.unprocessed.extend_from_slice(data);
+                return;
+            }
+            let (fillment, remainder) = data.split_at(missing);
+            self.unprocessed.extend_from_slice(fillment);
+            self.state = compress(self.state, &self.unprocessed);
+            self.processed = self.processed.wrapping_add(BLOCK_LENGTH_BYTES as u128);
+            self.unprocessed.clear();
+            data = remainder;
+        }
+
+        let mut chunks = data.chunks_exact(BLOCK_LENGTH_BYTES);
+        for chunk in chunks.by_ref() {
+            self.state = compress(self.state, chunk);
+            self.processed = self.processed.wrapping_add(BLOCK_LENGTH_BYTES as u128);
+        }
+        self.unprocessed.extend_from_slice(chunks.remainder());
+    }
+
+    /// Resets the internal state to the initial hash value.
+    pub(crate) fn reset(&mut self) {
+        self.state = self.initial;
+        self.unprocessed.clear();
+        self.processed = 0;
+    }
+
+    /// Applies padding and returns the final chaining state.
+    pub(crate) fn finalize(&self) -> [u64; STATE_LENGTH_QWORDS] {
+        let length = {
+            let length = self.processed.wrapping_add(self.unprocessed.len() as u128);
+            let length = length.wrapping_mul(8); // convert byte-length into bits-length
+            length.to_be_bytes()
+        };
+
+        let blocks = if self.unprocessed.len() + 1 + LENGTH_FIELD_BYTES <= BLOCK_LENGTH_BYTES {
+            1
+        } else {
+            2
+        };
+        let mut padding = [0u8; BLOCK_LENGTH_BYTES * 2];
+        padding[..self.unprocessed.len()].copy_from_slice(&self.unprocessed);
+        padding[self.unprocessed.len()] = 0x80;
+        padding[(BLOCK_LENGTH_BYTES * blocks - LENGTH_FIELD_BYTES)..(BLOCK_LENGTH_BYTES * blocks)]
+            .copy_from_slice(&length);
+
+        padding[..(BLOCK_LENGTH_BYTES * blocks)]
+            .chunks_exact(BLOCK_LENGTH_BYTES)
+            .fold(self.state, compress)
+    }
+
+    /// Serializes the in-progress state, see [`SHA2_512::export_state`](crate::SHA2_512::export_state) for the format.
+    pub(crate) fn export(&self, magic: &[u8; STATE_MAGIC_LENGTH_BYTES]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(STATE_HEADER_LENGTH_BYTES + self.unprocessed.len());
+        bytes.extend_from_slice(magic);
+        bytes.push(STATE_VERSION);
+        for word in self.state {
+            bytes.extend_from_slice(&word.to_be_bytes());
+        }
+        bytes.extend_from_slice(&self.processed.to_be_bytes());
+        // The buffer is drained after each full block, so its length always fits into a single byte.
+        bytes.push(self.unprocessed.len() as u8);
+        bytes.extend_from_slice(&self.unprocessed);
+        bytes
+    }
+
+    /// Deserializes and validates a state produced by [`Engine::export`].
+    pub(crate) fn import(
+        initial: [u64; STATE_LENGTH_QWORDS],
+        magic: &[u8; STATE_MAGIC_LENGTH_BYTES],
+        bytes: &[u8],
+    ) -> result::Result<Self, StateError> {
+        if bytes.len() < STATE_HEADER_LENGTH_BYTES {
+            let error = StateError::Truncated {
+                value: bytes.len(),
+                minimum: STATE_HEADER_LENGTH_BYTES,
+            };
+            return Err(error);
+        }
+        let (header, bytes) = bytes.split_at(STATE_MAGIC_LENGTH_BYTES);
+        if header != magic {
+            return Err(StateError::InvalidMagic);
+        }
+        let (version, bytes) = bytes.split_at(1);
+        if version[0] != STATE_VERSION {
+            return Err(StateError::UnsupportedVersion { version: version[0] });
+        }
+        let (words, bytes) = bytes.split_at(STATE_LENGTH_QWORDS * 8);
+        let mut state = [0u64; STATE_LENGTH_QWORDS];
+        for (word, chunk) in state.iter_mut().zip(words.chunks_exact(8)) {
+            *word = u64::from_be_bytes(chunk.try_into().expect("chunk length must be exact size as word"));
+        }
+        let (processed, bytes) = bytes.split_at(16);
+        let processed = u128::from_be_bytes(processed.try_into().expect("chunk length must be exact size as length"));
+        if processed % BLOCK_LENGTH_BYTES as u128 != 0 {
+            return Err(StateError::InvalidProcessedLength { value: processed });
+        }
+        let (length, unprocessed) = bytes.split_at(1);
+        let length = usize::from(length[0]);
+        if length >= BLOCK_LENGTH_BYTES {
+            return Err(StateError::InvalidBufferLength { value: length });
+        }
+        if unprocessed.len() != length {
+            let error = StateError::InvalidLength {
+                value: unprocessed.len(),
+                proper: length,
+            };
+            return Err(error);
+        }
+        let mut engine = Self::new(initial);
+        engine.state = state;
+        engine.processed = processed;
+        engine.unprocessed.extend_from_slice(unprocessed);
+        Ok(engine)
+    }
+
+    /// Produces the digest truncated to the given length.
+    pub(crate) fn digest<const LENGTH: usize>(&self) -> [u8; LENGTH] {
+        let state = self.finalize();
+        let mut digest = [0u8; LENGTH];
+        let bytes = state.iter().flat_map(|word| word.to_be_bytes());
+        for (byte, value) in digest.iter_mut().zip(bytes) {
+            *byte = value;
+        }
+        digest
+    }
+}
+
+/// Processes a single block and returns the updated chaining state.
+fn compress(state: [u64; STATE_LENGTH_QWORDS], block: &[u8]) -> [u64; STATE_LENGTH_QWORDS] {
+    fn small_sigma0(x: u64) -> u64 {
+        x.rotate_right(1) ^ x.rotate_right(8) ^ (x >> 7)
+    }
+
+    fn small_sigma1(x: u64) -> u64 {
+        x.rotate_right(19) ^ x.rotate_right(61) ^ (x >> 6)
+    }
+
+    fn capital_sigma0(x: u64) -> u64 {
+        x.rotate_right(28) ^ x.rotate_right(34) ^ x.rotate_right(39)
+    }
+
+    fn capital_sigma1(x: u64) -> u64 {
+        x.rotate_right(14) ^ x.rotate_right(18) ^ x.rotate_right(41)
+    }
+
+    fn ch(x: u64, y: u64, z: u64) -> u64 {
+        (x & y) ^ (!x & z)
+    }
+
+    fn maj(x: u64, y: u64, z: u64) -> u64 {
+        (x & y) ^ (x & z) ^ (y & z)
+    }
+
+    debug_assert_eq!(block.len(), BLOCK_LENGTH_BYTES, "block length must be exact");
+
+    let mut w = [0u64; 80];
+    for (word, chunk) in w.iter_mut().zip(block.chunks_exact(8)).take(BLOCK_LENGTH_QWORDS) {
+        *word = u64::from_be_bytes(chunk.try_into().expect("chunk length must be exact size as word"));
+    }
+    for i in BLOCK_LENGTH_QWORDS..80 {
+        w[i] = small_sigma1(w[i - 2])
+            .wrapping_add(w[i - 7])
+            .wrapping_add(small_sigma0(w[i - 15]))
+            .wrapping_add(w[i - 16]);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+    for (k, w) in K.iter().zip(w) {
+        let t1 = h
+            .wrapping_add(capital_sigma1(e))
+            .wrapping_add(ch(e, f, g))
+            .wrapping_add(*k)
+            .wrapping_add(w);
+        let t2 = capital_sigma0(a).wrapping_add(maj(a, b, c));
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+
+    let [h0, h1, h2, h3, h4, h5, h6, h7] = state;
+    [
+        h0.wrapping_add(a),
+        h1.wrapping_add(b),
+        h2.wrapping_add(c),
+        h3.wrapping_add(d),
+        h4.wrapping_add(e),
+        h5.wrapping_add(f),
+        h6.wrapping_add(g),
+        h7.wrapping_add(h),
+    ]
+}
+
+/// Generates the initial hash value for SHA-512/t as described in section 5.3.6 of FIPS 180-4.
+pub(crate) fn sha2_512_t_iv(t: usize) -> [u64; STATE_LENGTH_QWORDS] {
+    let initial = SHA2_512_IV.map(|word| word ^ 0xA5A5_A5A5_A5A5_A5A5);
+    let mut engine = Engine::new(initial);
+    engine.update(format!("SHA-512/{t}").as_bytes());
+    engine.finalize()
+}
@@ -0,0 +1,227 @@
+//! This module provides the HMAC-SHA-512 keyed hash function based on [RFC 2104](https://www.rfc-editor.org/rfc/rfc2104) and [FIPS PUB 198-1](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.198-1.pdf).
+//!
+//! The [`HmacSha512`] implements the [`Hash`](crate::Hash) trait, so anything that can be hashed with [`chksum`](crate::chksum) can be authenticated with [`chksum`] as well.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! # use chksum_sha2_512::Result;
+//! use chksum_sha2_512 as sha2_512;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let file = File::open(path)?;
+//! let digest = sha2_512::hmac::chksum(b"key", file)?;
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "8dd02f31d4668c38a984ebe98166abd97442499c2f739aa6ae377709eda97743ae63a27af1174a910ae12cd6cb5536ba3d6f6468990073047d7da6c5ac0aac24"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Debug, Formatter};
+use std::result;
+
+use crate::{compare, core, hash, Digest, ExpectedDigest, Result, VerifyError, SHA2_512};
+
+const INNER_PAD: u8 = 0x36;
+const OUTER_PAD: u8 = 0x5C;
+
+/// Creates a new HMAC instance with the given key.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512 as sha2_512;
+///
+/// let mut hmac = sha2_512::hmac::new(b"key");
+/// hmac.update(b"example data");
+/// let digest = hmac.finalize();
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "8dd02f31d4668c38a984ebe98166abd97442499c2f739aa6ae377709eda97743ae63a27af1174a910ae12cd6cb5536ba3d6f6468990073047d7da6c5ac0aac24"
+/// );
+/// ```
+#[must_use]
+pub fn new(key: impl AsRef<[u8]>) -> HmacSha512 {
+    HmacSha512::new(key)
+}
+
+/// Computes the HMAC of the given input.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512 as sha2_512;
+///
+/// let digest = sha2_512::hmac::hmac(b"key", b"example data");
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "8dd02f31d4668c38a984ebe98166abd97442499c2f739aa6ae377709eda97743ae63a27af1174a910ae12cd6cb5536ba3d6f6468990073047d7da6c5ac0aac24"
+/// );
+/// ```
+pub fn hmac(key: impl AsRef<[u8]>, data: impl core::Hashable) -> Digest {
+    let mut hmac = HmacSha512::new(key);
+    data.hash_with(&mut hmac);
+    hmac.finalize()
+}
+
+/// Computes the HMAC of the given input.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512 as sha2_512;
+///
+/// let data = b"example data";
+/// if let Ok(digest) = sha2_512::hmac::chksum(b"key", data) {
+///     assert_eq!(
+///         digest.to_hex_lowercase(),
+///         "8dd02f31d4668c38a984ebe98166abd97442499c2f739aa6ae377709eda97743ae63a27af1174a910ae12cd6cb5536ba3d6f6468990073047d7da6c5ac0aac24"
+///     );
+/// }
+/// ```
+pub fn chksum(key: impl AsRef<[u8]>, mut data: impl core::Chksumable) -> Result<Digest> {
+    let mut hmac = HmacSha512::new(key);
+    data.chksum_with(&mut hmac)?;
+    Ok(hmac.finalize())
+}
+
+/// Computes the HMAC of the given input.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512 as sha2_512;
+///
+/// # async fn wrapper() {
+/// let data = b"example data";
+/// if let Ok(digest) = sha2_512::hmac::async_chksum(b"key", data).await {
+///     assert_eq!(
+///         digest.to_hex_lowercase(),
+///         "8dd02f31d4668c38a984ebe98166abd97442499c2f739aa6ae377709eda97743ae63a27af1174a910ae12cd6cb5536ba3d6f6468990073047d7da6c5ac0aac24"
+///     );
+/// }
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum(key: impl AsRef<[u8]>, mut data: impl core::AsyncChksumable) -> Result<Digest> {
+    let mut hmac = HmacSha512::new(key);
+    data.chksum_with(&mut hmac).await?;
+    Ok(hmac.finalize())
+}
+
+/// The HMAC-SHA-512 instance.
+///
+/// The [`Default`] instance uses an empty key.
+#[derive(Clone)]
+pub struct HmacSha512 {
+    inner: SHA2_512,
+    outer: SHA2_512,
+    initial: SHA2_512,
+}
+
+impl HmacSha512 {
+    /// Creates a new HMAC instance with the given key.
+    ///
+    /// Keys longer than the block length are hashed first, as required by the specification.
+    #[must_use]
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        let key = key.as_ref();
+        let mut block = [0u8; hash::BLOCK_LENGTH_BYTES];
+        if key.len() > hash::BLOCK_LENGTH_BYTES {
+            let digest = SHA2_512::hash(key);
+            block[..hash::DIGEST_LENGTH_BYTES].copy_from_slice(digest.as_bytes());
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+        let mut inner = SHA2_512::new();
+        inner.update(block.map(|byte| byte ^ INNER_PAD));
+        let mut outer = SHA2_512::new();
+        outer.update(block.map(|byte| byte ^ OUTER_PAD));
+        let initial = inner.clone();
+        Self { inner, outer, initial }
+    }
+
+    /// Updates the HMAC state with an input data.
+    pub fn update<T>(&mut self, data: T)
+    where
+        T: AsRef<[u8]>,
+    {
+        self.inner.update(data);
+    }
+
+    /// Resets the HMAC state to its initial keyed state.
+    pub fn reset(&mut self) {
+        self.inner = self.initial.clone();
+    }
+
+    /// Produces the authentication code.
+    #[must_use]
+    pub fn finalize(&self) -> Digest {
+        let digest = self.inner.digest();
+        let mut outer = self.outer.clone();
+        outer.update(digest);
+        outer.digest()
+    }
+
+    /// Produces the authentication code and compares it with the expected one in constant time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VerifyError::Format`] if the expected code is malformed and [`VerifyError::Mismatch`] if the codes differ.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_512 as sha2_512;
+    ///
+    /// let mut hmac = sha2_512::hmac::new(b"key");
+    /// hmac.update(b"example data");
+    /// let result = hmac.verify(
+    ///     "8dd02f31d4668c38a984ebe98166abd97442499c2f739aa6ae377709eda97743ae63a27af1174a910ae12cd6cb5536ba3d6f6468990073047d7da6c5ac0aac24",
+    /// );
+    /// assert!(result.is_ok());
+    /// ```
+    pub fn verify(&self, expected: impl Into<ExpectedDigest>) -> result::Result<(), VerifyError> {
+        let ExpectedDigest(expected) = expected.into();
+        let expected = expected?;
+        let actual = self.finalize();
+        compare(expected, actual)
+    }
+}
+
+impl core::Hash for HmacSha512 {
+    type Digest = Digest;
+
+    fn update<T>(&mut self, data: T)
+    where
+        T: AsRef<[u8]>,
+    {
+        self.update(data);
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn digest(&self) -> Self::Digest {
+        self.finalize()
+    }
+}
+
+impl Default for HmacSha512 {
+    fn default() -> Self {
+        Self::new([])
+    }
+}
+
+impl Debug for HmacSha512 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // The internal state is derived from the key, so it is intentionally not printed.
+        f.debug_struct("HmacSha512").finish_non_exhaustive()
+    }
+}
@@ -233,14 +233,85 @@
 //! # }
 //! ```
 //!
+//! ## Directory Options
+//!
+//! Use the [`chksum_dir`] function to control which properties of a directory tree, like file names or sizes, contribute to the digest.
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use chksum_sha2_512 as sha2_512;
+//! use sha2_512::DirOptions;
+//!
+//! # fn wrapper(path: &Path) -> Result<(), sha2_512::DirError> {
+//! let options = DirOptions::new().include_paths(true).include_sizes(true);
+//! let digest = sha2_512::chksum_dir(path, &options)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Progress
+//!
+//! Use the [`progress`] module to receive progress reports while hashing large inputs.
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use chksum_sha2_512 as sha2_512;
+//! use sha2_512::progress::{self, Progress, ProgressReporter};
+//!
+//! # fn wrapper(path: &Path) -> sha2_512::Result<()> {
+//! let reporter =
+//!     ProgressReporter::new(|progress: &Progress| println!("{} bytes", progress.processed()));
+//! let digest = progress::chksum_file(path, reporter)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Cancellation
+//!
+//! Use the [`cancel`] module to stop hashing of large files and directories from another thread or task.
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use chksum_sha2_512 as sha2_512;
+//! use sha2_512::cancel::{self, CancelError, CancellationToken};
+//!
+//! # fn wrapper(path: &Path) -> Result<(), CancelError> {
+//! let token = CancellationToken::new();
+//! let digest = cancel::chksum(path, &token)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Variants
+//!
+//! The truncated variants of the hash function share the same engine and interface, each in its own module:
+//!
+//! * [`sha2_384`] with the [`SHA2_384`] struct,
+//! * [`sha2_512_224`] with the [`SHA2_512_224`] struct,
+//! * [`sha2_512_256`] with the [`SHA2_512_256`] struct.
+//!
+//! ```rust
+//! use chksum_sha2_512::sha2_384;
+//!
+//! let digest = sha2_384::hash(b"example data");
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "12ecdfd463a85a301b7c29a43bf4b19cdfc6e5e86a5f40396aa6ae3368a7e5b0ed31f3bef2eb3071577ba610b4ed1cb8"
+//! );
+//! ```
+//!
 //! # Features
 //!
 //! Cargo features are utilized to enable extra options.
 //!
 //! * `reader` enables the [`reader`] module with the [`Reader`] struct.
 //! * `writer` enables the [`writer`] module with the [`Writer`] struct.
+//! * `parallel` enables the [`par_chksum_dir`] function for hashing directories on multiple threads.
+//! * `ignore-rules` enables the [`IgnoreRules`] struct for filtering directory hashing with `.gitignore` patterns.
+//! * `mmap` enables the [`mmap`] module with the [`chksum_mmap`] function for hashing files through memory mapping.
+//! * `cli` enables the `chksum-sha2-512` binary, a drop-in replacement for the GNU coreutils `sha512sum` utility.
 //!
-//! By default, neither of these features is enabled.
+//! By default, none of these features is enabled.
 //!
 //! To customize your setup, disable the default features and enable only those that you need in your `Cargo.toml` file:
 //!
@@ -259,21 +330,42 @@
 //!
 //! * `async-runtime-tokio`: Enables async interface for Tokio runtime.
 //!
-//! By default, neither of these features is enabled.
+//! By default, this feature is not enabled.
 //!
 //! # License
 //!
 //! This crate is licensed under the MIT License.
 
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+#![cfg_attr(feature = "mmap", deny(unsafe_code))]
 
+pub mod block;
+pub mod cancel;
+pub mod dir;
+mod engine;
+pub mod hmac;
+pub mod manifest;
+pub mod merkle;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod progress;
+pub mod range;
 #[cfg(feature = "reader")]
 pub mod reader;
+pub mod sha2_384;
+pub mod sha2_512_224;
+pub mod sha2_512_256;
+pub mod sha2_512_t;
+mod variant;
 #[cfg(feature = "writer")]
 pub mod writer;
 
 use std::fmt::{self, Display, Formatter, LowerHex, UpperHex};
+use std::hash::{Hash as StdHash, Hasher};
+use std::hint::black_box;
+use std::result;
+use std::str::FromStr;
 
 use chksum_core as core;
 #[cfg(feature = "async-runtime-tokio")]
@@ -284,12 +376,50 @@ pub use chksum_core::{Chksumable, Error, Hash, Hashable, Result};
 #[doc(no_inline)]
 pub use chksum_hash_sha2_512 as hash;
 
+#[doc(inline)]
+pub use crate::block::{BlockDigests, BlockHasher};
+#[doc(inline)]
+pub use crate::cancel::CancellationToken;
+#[cfg(feature = "parallel")]
+#[doc(inline)]
+pub use crate::dir::par_chksum_dir;
+#[cfg(feature = "ignore-rules")]
+#[doc(inline)]
+pub use crate::dir::IgnoreRules;
+#[cfg(feature = "async-runtime-tokio")]
+#[doc(inline)]
+pub use crate::dir::{async_chksum_dir, async_chksum_dir_with_context};
+#[doc(inline)]
+pub use crate::dir::{chksum_dir, chksum_dir_with_context, DirContext, DirError, DirOptions, SymlinkPolicy};
+use crate::engine::Engine;
+#[doc(inline)]
+pub use crate::hmac::HmacSha512;
+#[doc(inline)]
+pub use crate::manifest::Manifest;
+#[doc(inline)]
+pub use crate::merkle::MerkleTree;
+#[cfg(feature = "mmap")]
+#[doc(inline)]
+pub use crate::mmap::{chksum_mmap, MmapError};
+#[doc(inline)]
+pub use crate::progress::{Progress, ProgressHook, ProgressReporter};
+#[cfg(feature = "async-runtime-tokio")]
+#[doc(inline)]
+pub use crate::range::async_chksum_range;
+#[doc(inline)]
+pub use crate::range::{chksum_range, RangeError};
 #[cfg(all(feature = "reader", feature = "async-runtime-tokio"))]
 #[doc(inline)]
 pub use crate::reader::AsyncReader;
 #[cfg(feature = "reader")]
 #[doc(inline)]
 pub use crate::reader::Reader;
+#[doc(inline)]
+pub use crate::sha2_384::SHA2_384;
+#[doc(inline)]
+pub use crate::sha2_512_224::SHA2_512_224;
+#[doc(inline)]
+pub use crate::sha2_512_256::SHA2_512_256;
 #[cfg(all(feature = "writer", feature = "async-runtime-tokio"))]
 #[doc(inline)]
 pub use crate::writer::AsyncWriter;
@@ -396,10 +526,72 @@ pub async fn async_chksum(data: impl core::AsyncChksumable) -> Result<Digest> {
     core::async_chksum::<SHA2_512>(data).await
 }
 
+/// Computes the hash of the given input and compares it with the expected digest.
+///
+/// The expected digest can be given as a [`Digest`], raw bytes or a hexadecimal string. The comparison is performed in constant time.
+///
+/// # Errors
+///
+/// Returns [`VerifyError::Format`] if the expected digest is malformed, [`VerifyError::Chksum`] if the input cannot be processed and [`VerifyError::Mismatch`] if the digests differ.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512 as sha2_512;
+///
+/// let data = b"example data";
+/// let result = sha2_512::verify(
+///     data,
+///     "ed59c5759a9ece516cec0c0623142d0e9fe70a27d750eee7fd38f4550d50addd873d0fa1a51fc823c1e3d5cada203f4a05d8325caacb7d3e0727a701f3f07e5f",
+/// );
+/// assert!(result.is_ok());
+/// ```
+pub fn verify(data: impl core::Chksumable, expected: impl Into<ExpectedDigest>) -> result::Result<(), VerifyError> {
+    let ExpectedDigest(expected) = expected.into();
+    let expected = expected?;
+    let actual = chksum(data)?;
+    compare(expected, actual)
+}
+
+/// Computes the hash of the given input and compares it with the expected digest.
+///
+/// # Errors
+///
+/// Returns [`VerifyError::Format`] if the expected digest is malformed, [`VerifyError::Chksum`] if the input cannot be processed and [`VerifyError::Mismatch`] if the digests differ.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512 as sha2_512;
+///
+/// # async fn wrapper() {
+/// let data = b"example data";
+/// let result = sha2_512::async_verify(
+///     data,
+///     "ed59c5759a9ece516cec0c0623142d0e9fe70a27d750eee7fd38f4550d50addd873d0fa1a51fc823c1e3d5cada203f4a05d8325caacb7d3e0727a701f3f07e5f",
+/// )
+/// .await;
+/// assert!(result.is_ok());
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_verify(
+    data: impl core::AsyncChksumable,
+    expected: impl Into<ExpectedDigest>,
+) -> result::Result<(), VerifyError> {
+    let ExpectedDigest(expected) = expected.into();
+    let expected = expected?;
+    let actual = async_chksum(data).await?;
+    compare(expected, actual)
+}
+
+/// Magic bytes identifying an exported [`SHA2_512`] state.
+const STATE_MAGIC: &[u8; engine::STATE_MAGIC_LENGTH_BYTES] = b"SHA2-512";
+
 /// The SHA-2 512 hash instance.
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SHA2_512 {
-    inner: hash::Update,
+    inner: Engine,
 }
 
 impl SHA2_512 {
@@ -444,7 +636,7 @@ impl SHA2_512 {
     /// ```
     #[must_use]
     pub fn new() -> Self {
-        let inner = hash::Update::new();
+        let inner = Engine::new(engine::SHA2_512_IV);
         Self { inner }
     }
 
@@ -469,7 +661,7 @@ impl SHA2_512 {
     where
         T: AsRef<[u8]>,
     {
-        self.inner.update(data);
+        self.inner.update(data.as_ref());
     }
 
     /// Resets the hash state to its initial state.
@@ -508,7 +700,61 @@ impl SHA2_512 {
     /// ```
     #[must_use]
     pub fn digest(&self) -> Digest {
-        self.inner.digest().into()
+        Digest::new(self.inner.digest())
+    }
+
+    /// Exports the in-progress hash state, so the computation can be resumed later with [`SHA2_512::import_state`].
+    ///
+    /// The exported state is a byte blob with the following layout, all integers are big-endian:
+    ///
+    /// | Offset | Length | Content                                           |
+    /// |--------|--------|---------------------------------------------------|
+    /// | 0      | 8      | magic bytes `SHA2-512`                            |
+    /// | 8      | 1      | format version, currently `1`                     |
+    /// | 9      | 64     | eight 64-bit chaining words                       |
+    /// | 73     | 16     | 128-bit number of bytes processed in full blocks  |
+    /// | 89     | 1      | length of the buffered tail, less than 128        |
+    /// | 90     | *n*    | buffered tail bytes                               |
+    ///
+    /// **Warning!** The exported state allows computing digests of any data that extends the already processed input, so treat it as confidential if the input is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_512::SHA2_512;
+    ///
+    /// let mut hash = SHA2_512::new();
+    /// hash.update(b"example");
+    /// let state = hash.export_state();
+    ///
+    /// let mut hash = SHA2_512::import_state(&state)?;
+    /// hash.update(b" data");
+    /// let digest = hash.digest();
+    /// assert_eq!(
+    ///     digest.to_hex_lowercase(),
+    ///     "ed59c5759a9ece516cec0c0623142d0e9fe70a27d750eee7fd38f4550d50addd873d0fa1a51fc823c1e3d5cada203f4a05d8325caacb7d3e0727a701f3f07e5f"
+    /// );
+    /// # Ok::<(), chksum_sha2_512::StateError>(())
+    /// ```
+    #[must_use]
+    pub fn export_state(&self) -> Vec<u8> {
+        self.inner.export(STATE_MAGIC)
+    }
+
+    /// Imports a hash state previously produced by [`SHA2_512::export_state`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StateError`] if the state is truncated, was exported by a different algorithm or format version, or is internally inconsistent.
+    pub fn import_state(state: &[u8]) -> result::Result<Self, StateError> {
+        let inner = Engine::import(engine::SHA2_512_IV, STATE_MAGIC, state)?;
+        Ok(Self { inner })
+    }
+}
+
+impl Default for SHA2_512 {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -532,6 +778,9 @@ impl core::Hash for SHA2_512 {
 }
 
 /// A hash digest.
+///
+/// Equality comparisons are performed in constant time, see [`Digest::ct_eq`].
+#[derive(Clone, Debug)]
 pub struct Digest(hash::Digest);
 
 impl Digest {
@@ -631,10 +880,89 @@ impl Digest {
         let Self(inner) = self;
         inner.to_hex_uppercase()
     }
+
+    /// Parses a digest from its hexadecimal representation.
+    ///
+    /// Both lowercase and uppercase digits are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError::InvalidLength`] if the input is not exactly [`LENGTH_HEX`](hash::digest::LENGTH_HEX) bytes long and [`FormatError::InvalidCharacter`] with the byte offset of the first character that is not a hexadecimal digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_512 as sha2_512;
+    ///
+    /// let digest = sha2_512::Digest::from_hex(
+    ///     "CF83E1357EEFB8BDF1542850D66D8007D620E4050B5715DC83F4A921D36CE9CE47D0D13C5D85F2B0FF8318D2877EEC2F63B931BD47417A81A538327AF927DA3E",
+    /// )?;
+    /// assert_eq!(
+    ///     digest.to_hex_lowercase(),
+    ///     "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+    /// );
+    /// # Ok::<(), sha2_512::FormatError>(())
+    /// ```
+    pub fn from_hex(digest: &str) -> result::Result<Self, FormatError> {
+        decode_hex(digest).map(Self::new)
+    }
+
+    /// Compares two digests in constant time.
+    ///
+    /// The running time depends only on the digest length, not on the position of the first differing byte, which makes it suitable for verifying untrusted checksums.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_512 as sha2_512;
+    ///
+    /// let digest = sha2_512::hash(b"example data");
+    /// assert!(digest.ct_eq(&sha2_512::hash(b"example data")));
+    /// assert!(!digest.ct_eq(&sha2_512::hash(b"other data")));
+    /// ```
+    #[must_use]
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq(self.as_bytes(), other.as_bytes())
+    }
 }
 
 impl core::Digest for Digest {}
 
+impl PartialEq for Digest {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl Eq for Digest {}
+
+impl PartialEq<[u8]> for Digest {
+    fn eq(&self, other: &[u8]) -> bool {
+        ct_eq(self.as_bytes(), other)
+    }
+}
+
+impl PartialEq<str> for Digest {
+    fn eq(&self, other: &str) -> bool {
+        Self::from_hex(other).is_ok_and(|other| self.ct_eq(&other))
+    }
+}
+
+impl PartialEq<&str> for Digest {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl StdHash for Digest {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.as_bytes().hash(state);
+    }
+}
+
 impl AsRef<[u8]> for Digest {
     fn as_ref(&self) -> &[u8] {
         let Self(inner) = self;
@@ -674,3 +1002,196 @@ impl From<hash::Digest> for Digest {
         Self(digest)
     }
 }
+
+impl FromStr for Digest {
+    type Err = FormatError;
+
+    fn from_str(digest: &str) -> result::Result<Self, Self::Err> {
+        Self::from_hex(digest)
+    }
+}
+
+impl TryFrom<&str> for Digest {
+    type Error = FormatError;
+
+    fn try_from(digest: &str) -> result::Result<Self, Self::Error> {
+        Self::from_hex(digest)
+    }
+}
+
+/// An expected digest used by [`verify`].
+///
+/// It can be created from a [`Digest`], raw digest bytes or a hexadecimal string. Malformed input is reported by [`verify`] before any data is processed.
+#[derive(Clone, Debug)]
+pub struct ExpectedDigest(result::Result<Digest, FormatError>);
+
+impl From<Digest> for ExpectedDigest {
+    fn from(digest: Digest) -> Self {
+        Self(Ok(digest))
+    }
+}
+
+impl From<&Digest> for ExpectedDigest {
+    fn from(digest: &Digest) -> Self {
+        Self(Ok(digest.clone()))
+    }
+}
+
+impl From<[u8; hash::DIGEST_LENGTH_BYTES]> for ExpectedDigest {
+    fn from(digest: [u8; hash::DIGEST_LENGTH_BYTES]) -> Self {
+        Self(Ok(Digest::new(digest)))
+    }
+}
+
+impl From<&[u8]> for ExpectedDigest {
+    fn from(digest: &[u8]) -> Self {
+        let digest = <[u8; hash::DIGEST_LENGTH_BYTES]>::try_from(digest)
+            .map(Digest::new)
+            .map_err(|_| {
+                FormatError::InvalidLength {
+                    value: digest.len(),
+                    proper: hash::DIGEST_LENGTH_BYTES,
+                }
+            });
+        Self(digest)
+    }
+}
+
+impl From<&str> for ExpectedDigest {
+    fn from(digest: &str) -> Self {
+        Self(Digest::from_hex(digest))
+    }
+}
+
+impl From<&String> for ExpectedDigest {
+    fn from(digest: &String) -> Self {
+        Self::from(digest.as_str())
+    }
+}
+
+impl From<String> for ExpectedDigest {
+    fn from(digest: String) -> Self {
+        Self::from(digest.as_str())
+    }
+}
+
+/// An error type for the hash state import.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum StateError {
+    /// The state is shorter than its fixed-size header.
+    #[error("truncated state of length `{value}`, minimum length `{minimum}`")]
+    Truncated { value: usize, minimum: usize },
+    /// The state does not start with the expected magic bytes.
+    #[error("invalid state magic bytes")]
+    InvalidMagic,
+    /// The state format version is not supported.
+    #[error("unsupported state version `{version}`")]
+    UnsupportedVersion { version: u8 },
+    /// The number of processed bytes is not a multiple of the block length.
+    #[error("invalid processed length `{value}`")]
+    InvalidProcessedLength { value: u128 },
+    /// The declared buffered tail is not shorter than a block.
+    #[error("invalid buffer length `{value}`")]
+    InvalidBufferLength { value: usize },
+    /// The buffered tail length differs from the declared one.
+    #[error("invalid length `{value}`, proper value `{proper}`")]
+    InvalidLength { value: usize, proper: usize },
+}
+
+/// An error type for the digest verification.
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError {
+    /// The input cannot be processed.
+    #[error(transparent)]
+    Chksum(#[from] Error),
+    /// The expected digest is malformed.
+    #[error(transparent)]
+    Format(#[from] FormatError),
+    /// The computed digest differs from the expected one.
+    #[error("digest mismatch, expected `{expected}`, actual `{actual}`")]
+    Mismatch { expected: Box<Digest>, actual: Box<Digest> },
+}
+
+/// An error type for the digest parsing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum FormatError {
+    /// The input has an invalid length.
+    #[error("invalid length `{value}`, proper value `{proper}`")]
+    InvalidLength { value: usize, proper: usize },
+    /// The input contains a character that is not a hexadecimal digit.
+    #[error("invalid character `{character}` at offset `{offset}`")]
+    InvalidCharacter { character: char, offset: usize },
+}
+
+/// Compares the expected digest with the actual one in constant time.
+fn compare(expected: Digest, actual: Digest) -> result::Result<(), VerifyError> {
+    if expected.ct_eq(&actual) {
+        Ok(())
+    } else {
+        let expected = Box::new(expected);
+        let actual = Box::new(actual);
+        Err(VerifyError::Mismatch { expected, actual })
+    }
+}
+
+/// Decodes a hexadecimal string of either case into digest bytes.
+fn decode_hex<const LENGTH: usize>(digest: &str) -> result::Result<[u8; LENGTH], FormatError> {
+    if digest.len() != LENGTH * 2 {
+        let error = FormatError::InvalidLength {
+            value: digest.len(),
+            proper: LENGTH * 2,
+        };
+        return Err(error);
+    }
+    if let Some((offset, character)) = digest
+        .char_indices()
+        .find(|(_, character)| !character.is_ascii_hexdigit())
+    {
+        let error = FormatError::InvalidCharacter { character, offset };
+        return Err(error);
+    }
+    let mut bytes = [0u8; LENGTH];
+    for (byte, pair) in bytes.iter_mut().zip(digest.as_bytes().chunks_exact(2)) {
+        let [high, low] = [pair[0], pair[1]].map(|digit| {
+            match digit {
+                b'0'..=b'9' => digit - b'0',
+                b'a'..=b'f' => digit - b'a' + 10,
+                b'A'..=b'F' => digit - b'A' + 10,
+                _ => unreachable!("digit must be validated as hexadecimal"),
+            }
+        });
+        *byte = (high << 4) | low;
+    }
+    Ok(bytes)
+}
+
+/// Formats digest bytes as a hexadecimal string, honoring the alternate flag and padding.
+fn fmt_hex(bytes: &[u8], formatter: &mut Formatter<'_>, uppercase: bool) -> fmt::Result {
+    let digits = if uppercase {
+        b"0123456789ABCDEF"
+    } else {
+        b"0123456789abcdef"
+    };
+    let digest: String = bytes
+        .iter()
+        .flat_map(|byte| [digits[usize::from(byte >> 4)], digits[usize::from(byte & 0x0F)]])
+        .map(char::from)
+        .collect();
+    if formatter.alternate() {
+        formatter.pad_integral(true, if uppercase { "0X" } else { "0x" }, &digest)
+    } else {
+        formatter.pad(&digest)
+    }
+}
+
+/// Compares two byte slices in constant time with respect to their contents.
+fn ct_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    let difference = lhs
+        .iter()
+        .zip(rhs)
+        .fold(0u8, |difference, (lhs, rhs)| difference | (lhs ^ rhs));
+    black_box(difference) == 0
+}
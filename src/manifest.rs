@@ -0,0 +1,881 @@
+//! This module provides checksum manifests compatible with the GNU coreutils `sha512sum` utility.
+//!
+//! A [`Manifest`] is a list of entries, each consisting of a digest, a path and a [`Mode`]. It is written as one line per entry:
+//!
+//! ```text
+//! <hex digest> <mode><path>
+//! ```
+//!
+//! where the mode is a space for the [`Text`](Mode::Text) and an asterisk for the [`Binary`](Mode::Binary) mode.
+//!
+//! Alternatively, a manifest can be written in the BSD tag [`Format`], as produced by `sha512sum --tag` and the BSD `sha512` utility:
+//!
+//! ```text
+//! SHA512 (<path>) = <hex digest>
+//! ```
+//!
+//! In both formats, if the path contains a backslash, a line feed or a carriage return, the line starts with a backslash and these characters are escaped in the path as `\\`, `\n` and `\r` respectively.
+//!
+//! The format of every line is detected automatically when parsing, so a manifest can mix both. Tagged lines of algorithms other than `SHA512` are rejected. Blank lines and lines starting with `#` are skipped and a carriage return before the line feed is ignored.
+//!
+//! # Verification
+//!
+//! The [`Manifest::verify`] function is the equivalent of `sha512sum --check`. It computes the digests of the files referenced by the manifest and returns a [`Report`] with a [`Status`] of every entry.
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs;
+//!
+//! use chksum_sha2_512 as sha2_512;
+//! use sha2_512::manifest::{Manifest, VerifyOptions};
+//!
+//! # fn wrapper(dir: &Path) -> Result<(), sha2_512::manifest::ManifestError> {
+//! let manifest = Manifest::parse_lenient(fs::read(dir.join("SHA512SUMS"))?);
+//! let options = VerifyOptions::new()
+//!     .with_base_dir(dir)
+//!     .with_ignore_missing(true);
+//! let report = manifest.verify(&options);
+//! for entry in report.entries() {
+//!     println!("{}: {}", entry.path().display(), entry.status());
+//! }
+//! assert!(report.is_success());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha2_512 as sha2_512;
+//! use sha2_512::manifest::{Entry, Manifest, Mode};
+//!
+//! # fn wrapper() -> Result<(), sha2_512::manifest::ManifestError> {
+//! let mut manifest = Manifest::new();
+//! manifest.push(Entry::new(sha2_512::hash(b"example data"), "data.txt", Mode::Binary));
+//! let text = manifest.to_string();
+//! assert_eq!(
+//!     text,
+//!     "ed59c5759a9ece516cec0c0623142d0e9fe70a27d750eee7fd38f4550d50addd873d0fa1a51fc823c1e3d5cada203f4a05d8325caacb7d3e0727a701f3f07e5f *data.txt\n"
+//! );
+//!
+//! let parsed: Manifest = text.parse()?;
+//! assert_eq!(parsed, manifest);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::result;
+use std::str::FromStr;
+
+#[cfg(feature = "async-runtime-tokio")]
+use crate::async_chksum;
+use crate::{chksum, Digest, Error, Result};
+
+const DIGEST_LENGTH_HEX: usize = crate::hash::DIGEST_LENGTH_BYTES * 2;
+const TAG: &[u8] = b"SHA512";
+
+/// The mode in which a file was read when computing its digest.
+///
+/// The mode only affects the manifest line, files are always hashed as binary data.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Mode {
+    /// The text mode, marked with a space.
+    #[default]
+    Text,
+    /// The binary mode, marked with an asterisk.
+    Binary,
+}
+
+impl Mode {
+    const fn marker(self) -> u8 {
+        match self {
+            Self::Text => b' ',
+            Self::Binary => b'*',
+        }
+    }
+
+    const fn from_marker(marker: u8) -> Option<Self> {
+        match marker {
+            b' ' => Some(Self::Text),
+            b'*' => Some(Self::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// The manifest line format.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Format {
+    /// The GNU coreutils format, `<hex digest> <mode><path>`.
+    #[default]
+    Gnu,
+    /// The BSD tag format, `SHA512 (<path>) = <hex digest>`.
+    Bsd,
+}
+
+/// A single manifest entry.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    digest: Digest,
+    path: PathBuf,
+    mode: Mode,
+}
+
+impl Entry {
+    /// Creates a new entry.
+    #[must_use]
+    pub fn new(digest: Digest, path: impl Into<PathBuf>, mode: Mode) -> Self {
+        let path = path.into();
+        Self { digest, path, mode }
+    }
+
+    /// Returns the digest.
+    #[must_use]
+    pub const fn digest(&self) -> &Digest {
+        &self.digest
+    }
+
+    /// Returns the path.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the mode.
+    #[must_use]
+    pub const fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Writes the entry as a single manifest line, including the line feed.
+    fn write(&self, writer: &mut impl Write, format: Format) -> io::Result<()> {
+        let mut path = encode(&self.path);
+        if needs_escaping(&path) {
+            writer.write_all(b"\\")?;
+            path = escape(&path);
+        }
+        let digest = self.digest.to_hex_lowercase();
+        match format {
+            Format::Gnu => {
+                writer.write_all(digest.as_bytes())?;
+                writer.write_all(&[b' ', self.mode.marker()])?;
+                writer.write_all(&path)?;
+            },
+            Format::Bsd => {
+                writer.write_all(TAG)?;
+                writer.write_all(b" (")?;
+                writer.write_all(&path)?;
+                writer.write_all(b") = ")?;
+                writer.write_all(digest.as_bytes())?;
+            },
+        }
+        writer.write_all(b"\n")
+    }
+
+    /// Parses a single manifest line without the line feed, detecting its format.
+    fn parse(line: &[u8]) -> result::Result<Self, LineError> {
+        let (escaped, line) = match line.strip_prefix(b"\\") {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+        // A BSD line with a long path can have the GNU separator at the digest offset.
+        let (digest, path, mode) = match Self::split_gnu(line) {
+            Some(fields) if fields.0.iter().all(u8::is_ascii_hexdigit) => fields,
+            _ => Self::split_bsd(line)?,
+        };
+        let digest = std::str::from_utf8(digest).map_err(|_| LineError::Malformed)?;
+        let digest = Digest::from_hex(digest).map_err(|_| LineError::Malformed)?;
+        let path = if escaped { unescape(path) } else { Some(path.to_vec()) };
+        let path = path.and_then(decode).ok_or(LineError::Malformed)?;
+        Ok(Self { digest, path, mode })
+    }
+
+    /// Splits the GNU line into the digest, the path and the mode.
+    fn split_gnu(line: &[u8]) -> Option<(&[u8], &[u8], Mode)> {
+        if line.len() < DIGEST_LENGTH_HEX + 3 || line[DIGEST_LENGTH_HEX] != b' ' {
+            return None;
+        }
+        let mode = Mode::from_marker(line[DIGEST_LENGTH_HEX + 1])?;
+        Some((&line[..DIGEST_LENGTH_HEX], &line[DIGEST_LENGTH_HEX + 2..], mode))
+    }
+
+    /// Splits the BSD line into the digest, the path and the mode, which is always binary.
+    fn split_bsd(line: &[u8]) -> result::Result<(&[u8], &[u8], Mode), LineError> {
+        let separator = line
+            .windows(2)
+            .position(|window| window == b" (")
+            .ok_or(LineError::Malformed)?;
+        let (tag, rest) = (&line[..separator], &line[separator + 2..]);
+        if tag.is_empty() || !tag.iter().all(|byte| byte.is_ascii_alphanumeric() || *byte == b'-') {
+            return Err(LineError::Malformed);
+        }
+        if tag != TAG {
+            let tag = String::from_utf8_lossy(tag).into_owned();
+            return Err(LineError::UnsupportedAlgorithm(tag));
+        }
+        let separator = rest
+            .windows(4)
+            .rposition(|window| window == b") = ")
+            .ok_or(LineError::Malformed)?;
+        let (path, digest) = (&rest[..separator], &rest[separator + 4..]);
+        if path.is_empty() || digest.len() != DIGEST_LENGTH_HEX {
+            return Err(LineError::Malformed);
+        }
+        Ok((digest, path, Mode::Binary))
+    }
+}
+
+/// A checksum manifest, the content of a `SHA512SUMS` file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Manifest {
+    entries: Vec<Entry>,
+    malformed: Vec<usize>,
+}
+
+impl Manifest {
+    /// Creates an empty manifest.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            malformed: Vec::new(),
+        }
+    }
+
+    /// Creates a manifest by computing the digests of the given paths.
+    ///
+    /// The paths are recorded as given.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error that occurred while computing a digest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// use chksum_sha2_512 as sha2_512;
+    /// use sha2_512::manifest::{Manifest, Mode};
+    ///
+    /// # fn wrapper(first: &Path, second: &Path) -> sha2_512::Result<()> {
+    /// let manifest = Manifest::from_paths([first, second], Mode::Text)?;
+    /// assert_eq!(manifest.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_paths<I, P>(paths: I, mode: Mode) -> Result<Self>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut manifest = Self::new();
+        for path in paths {
+            let path = path.as_ref();
+            let digest = chksum(path)?;
+            manifest.push(Entry::new(digest, path, mode));
+        }
+        Ok(manifest)
+    }
+
+    /// Creates a manifest by computing the digests of the given paths.
+    ///
+    /// The paths are recorded as given.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error that occurred while computing a digest.
+    #[cfg(feature = "async-runtime-tokio")]
+    pub async fn async_from_paths<I, P>(paths: I, mode: Mode) -> Result<Self>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut manifest = Self::new();
+        for path in paths {
+            let path = path.as_ref();
+            let digest = async_chksum(path).await?;
+            manifest.push(Entry::new(digest, path, mode));
+        }
+        Ok(manifest)
+    }
+
+    /// Parses a manifest from its bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManifestError::Malformed`] with the number of the first line that is not a valid manifest line and [`ManifestError::UnsupportedAlgorithm`] if a tagged line uses an algorithm other than `SHA512`.
+    pub fn parse(input: impl AsRef<[u8]>) -> result::Result<Self, ManifestError> {
+        Self::read(input.as_ref())
+    }
+
+    /// Reads a manifest from the reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManifestError::Io`] if the reader fails, [`ManifestError::Malformed`] with the number of the first line that is not a valid manifest line and [`ManifestError::UnsupportedAlgorithm`] if a tagged line uses an algorithm other than `SHA512`.
+    pub fn read(reader: impl BufRead) -> result::Result<Self, ManifestError> {
+        match Self::read_lines(reader, true)? {
+            (_, Some(error)) => Err(error),
+            (manifest, None) => Ok(manifest),
+        }
+    }
+
+    /// Parses a manifest from its bytes, skipping malformed lines and lines of unsupported algorithms.
+    ///
+    /// The numbers of the skipped lines are available through [`Manifest::malformed_lines`].
+    #[must_use]
+    pub fn parse_lenient(input: impl AsRef<[u8]>) -> Self {
+        // Reading from a slice never fails.
+        Self::read_lines(input.as_ref(), false)
+            .map(|(manifest, _)| manifest)
+            .unwrap_or_default()
+    }
+
+    /// Reads a manifest from the reader, skipping malformed lines and lines of unsupported algorithms.
+    ///
+    /// The numbers of the skipped lines are available through [`Manifest::malformed_lines`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader fails.
+    pub fn read_lenient(reader: impl BufRead) -> io::Result<Self> {
+        Self::read_lines(reader, false).map(|(manifest, _)| manifest)
+    }
+
+    /// Reads the lines, stopping at the first invalid one if `strict` is set and returning its error.
+    fn read_lines(mut reader: impl BufRead, strict: bool) -> io::Result<(Self, Option<ManifestError>)> {
+        let mut manifest = Self::new();
+        let mut buffer = Vec::new();
+        let mut line = 0;
+        loop {
+            buffer.clear();
+            if reader.read_until(b'\n', &mut buffer)? == 0 {
+                break;
+            }
+            line += 1;
+            let content = buffer.strip_suffix(b"\n").unwrap_or(&buffer);
+            let content = content.strip_suffix(b"\r").unwrap_or(content);
+            // Like `sha512sum --check`, blank lines and comments are skipped.
+            if content.is_empty() || content.starts_with(b"#") {
+                continue;
+            }
+            match Entry::parse(content) {
+                Ok(entry) => manifest.push(entry),
+                Err(error) if strict => {
+                    let error = match error {
+                        LineError::Malformed => ManifestError::Malformed { line },
+                        LineError::UnsupportedAlgorithm(algorithm) => {
+                            ManifestError::UnsupportedAlgorithm { line, algorithm }
+                        },
+                    };
+                    return Ok((manifest, Some(error)));
+                },
+                Err(_) => manifest.malformed.push(line),
+            }
+        }
+        Ok((manifest, None))
+    }
+
+    /// Computes the digests of the referenced files and compares them with the expected ones.
+    ///
+    /// Entries are verified in order and every entry is reported, see [`Report`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// use chksum_sha2_512 as sha2_512;
+    /// use sha2_512::manifest::{Manifest, Mode, Status, VerifyOptions};
+    ///
+    /// # fn wrapper(path: &Path) -> sha2_512::Result<()> {
+    /// let manifest = Manifest::from_paths([path], Mode::Text)?;
+    /// let report = manifest.verify(&VerifyOptions::new());
+    /// assert_eq!(report.entries()[0].status(), Status::Ok);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn verify(&self, options: &VerifyOptions) -> Report {
+        let mut report = Report::new(self, options);
+        for entry in &self.entries {
+            let path = options.resolve(&entry.path);
+            let result = File::open(path).map_err(Error::from).and_then(chksum);
+            report.push(entry, result);
+        }
+        report
+    }
+
+    /// Computes the digests of the referenced files and compares them with the expected ones.
+    ///
+    /// The report is identical to the one returned by [`Manifest::verify`].
+    #[cfg(feature = "async-runtime-tokio")]
+    pub async fn async_verify(&self, options: &VerifyOptions) -> Report {
+        let mut report = Report::new(self, options);
+        for entry in &self.entries {
+            let path = options.resolve(&entry.path);
+            let result = match tokio::fs::File::open(path).await {
+                Ok(file) => async_chksum(file).await,
+                Err(error) => Err(error.into()),
+            };
+            report.push(entry, result);
+        }
+        report
+    }
+
+    /// Writes the manifest in the GNU format to the writer.
+    ///
+    /// Unlike the [`Display`] implementation, paths are written as raw bytes on Unix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the writer fails.
+    pub fn write(&self, writer: impl Write) -> io::Result<()> {
+        self.write_with_format(writer, Format::Gnu)
+    }
+
+    /// Writes the manifest in the given format to the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the writer fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha2_512 as sha2_512;
+    /// use sha2_512::manifest::{Entry, Format, Manifest, Mode};
+    ///
+    /// let manifest: Manifest = [Entry::new(sha2_512::hash(b""), "empty", Mode::Binary)]
+    ///     .into_iter()
+    ///     .collect();
+    /// let mut bytes = Vec::new();
+    /// manifest.write_with_format(&mut bytes, Format::Bsd).unwrap();
+    /// assert!(bytes.starts_with(b"SHA512 (empty) = cf83e135"));
+    /// ```
+    pub fn write_with_format(&self, mut writer: impl Write, format: Format) -> io::Result<()> {
+        for entry in &self.entries {
+            entry.write(&mut writer, format)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the manifest in the GNU format as bytes, see [`Manifest::write`].
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes_with_format(Format::Gnu)
+    }
+
+    /// Returns the manifest in the given format as bytes, see [`Manifest::write_with_format`].
+    #[must_use]
+    pub fn to_bytes_with_format(&self, format: Format) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for entry in &self.entries {
+            // Writing to a vector never fails.
+            let _ = entry.write(&mut bytes, format);
+        }
+        bytes
+    }
+
+    /// Appends an entry.
+    pub fn push(&mut self, entry: Entry) {
+        self.entries.push(entry);
+    }
+
+    /// Returns the entries.
+    #[must_use]
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Returns the numbers of the malformed lines skipped by the lenient parsing, numbered from one.
+    #[must_use]
+    pub fn malformed_lines(&self) -> &[usize] {
+        &self.malformed
+    }
+
+    /// Returns the number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the manifest has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Display for Manifest {
+    /// Formats the manifest, replacing invalid UTF-8 sequences in paths with `U+FFFD`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.to_bytes()))
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = ManifestError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl FromIterator<Entry> for Manifest {
+    fn from_iter<T: IntoIterator<Item = Entry>>(iter: T) -> Self {
+        let entries = iter.into_iter().collect();
+        let malformed = Vec::new();
+        Self { entries, malformed }
+    }
+}
+
+impl Extend<Entry> for Manifest {
+    fn extend<T: IntoIterator<Item = Entry>>(&mut self, iter: T) {
+        self.entries.extend(iter);
+    }
+}
+
+impl IntoIterator for Manifest {
+    type IntoIter = std::vec::IntoIter<Entry>;
+    type Item = Entry;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Manifest {
+    type IntoIter = std::slice::Iter<'a, Entry>;
+    type Item = &'a Entry;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+/// Options of the manifest verification.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512::manifest::VerifyOptions;
+///
+/// let options = VerifyOptions::new()
+///     .with_ignore_missing(true)
+///     .with_strict(true)
+///     .with_base_dir("release");
+/// assert!(options.strict());
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VerifyOptions {
+    ignore_missing: bool,
+    strict: bool,
+    base_dir: Option<PathBuf>,
+}
+
+impl VerifyOptions {
+    /// Creates default options.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ignore_missing: false,
+            strict: false,
+            base_dir: None,
+        }
+    }
+
+    /// Sets whether missing files do not cause the verification to fail, like `--ignore-missing`.
+    #[must_use]
+    pub const fn with_ignore_missing(mut self, ignore_missing: bool) -> Self {
+        self.ignore_missing = ignore_missing;
+        self
+    }
+
+    /// Sets whether malformed lines cause the verification to fail, like `--strict`.
+    #[must_use]
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the directory relative paths are resolved against, instead of the current working directory.
+    #[must_use]
+    pub fn with_base_dir(mut self, base_dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = Some(base_dir.into());
+        self
+    }
+
+    /// Returns whether missing files do not cause the verification to fail.
+    #[must_use]
+    pub const fn ignore_missing(&self) -> bool {
+        self.ignore_missing
+    }
+
+    /// Returns whether malformed lines cause the verification to fail.
+    #[must_use]
+    pub const fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns the directory relative paths are resolved against.
+    #[must_use]
+    pub fn base_dir(&self) -> Option<&Path> {
+        self.base_dir.as_deref()
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        match &self.base_dir {
+            Some(base_dir) => base_dir.join(path),
+            None => path.to_path_buf(),
+        }
+    }
+}
+
+/// The verification status of a single manifest entry.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Status {
+    /// The digest matches.
+    Ok,
+    /// The digest does not match.
+    Failed,
+    /// The file does not exist.
+    Missing,
+    /// The file cannot be read.
+    Unreadable {
+        /// The kind of the error.
+        kind: io::ErrorKind,
+    },
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ok => f.write_str("OK"),
+            Self::Failed => f.write_str("FAILED"),
+            Self::Missing => f.write_str("FAILED open or read (missing)"),
+            Self::Unreadable { kind } => write!(f, "FAILED open or read ({kind})"),
+        }
+    }
+}
+
+/// The verification result of a single manifest entry.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntryReport {
+    path: PathBuf,
+    status: Status,
+    error: Option<String>,
+}
+
+impl EntryReport {
+    /// Returns the path as written in the manifest.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the status.
+    #[must_use]
+    pub const fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Returns the message of the error that prevented reading a missing or unreadable file.
+    #[must_use]
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+/// The result of the manifest verification.
+///
+/// The verification is successful if at least one file was verified, no digest mismatched, no file was unreadable, no file was missing unless missing files are ignored and no line was malformed in the strict mode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report {
+    entries: Vec<EntryReport>,
+    malformed: Vec<usize>,
+    ignore_missing: bool,
+    strict: bool,
+}
+
+impl Report {
+    fn new(manifest: &Manifest, options: &VerifyOptions) -> Self {
+        Self {
+            entries: Vec::with_capacity(manifest.entries.len()),
+            malformed: manifest.malformed.clone(),
+            ignore_missing: options.ignore_missing,
+            strict: options.strict,
+        }
+    }
+
+    fn push(&mut self, entry: &Entry, result: Result<Digest>) {
+        let status = match &result {
+            Ok(digest) if digest == &entry.digest => Status::Ok,
+            Ok(_) => Status::Failed,
+            Err(Error::Io(error)) if error.kind() == io::ErrorKind::NotFound => Status::Missing,
+            Err(Error::Io(error)) => Status::Unreadable { kind: error.kind() },
+            Err(Error::IsTerminal) => {
+                Status::Unreadable {
+                    kind: io::ErrorKind::Unsupported,
+                }
+            },
+        };
+        let path = entry.path.clone();
+        let error = result.err().map(|error| error.to_string());
+        self.entries.push(EntryReport { path, status, error });
+    }
+
+    /// Returns the results of entries in the manifest order.
+    #[must_use]
+    pub fn entries(&self) -> &[EntryReport] {
+        &self.entries
+    }
+
+    /// Returns the numbers of the malformed manifest lines.
+    #[must_use]
+    pub fn malformed_lines(&self) -> &[usize] {
+        &self.malformed
+    }
+
+    /// Returns the number of matching digests.
+    #[must_use]
+    pub fn ok(&self) -> usize {
+        self.count(|status| status == Status::Ok)
+    }
+
+    /// Returns the number of mismatched digests.
+    #[must_use]
+    pub fn failed(&self) -> usize {
+        self.count(|status| status == Status::Failed)
+    }
+
+    /// Returns the number of missing files.
+    #[must_use]
+    pub fn missing(&self) -> usize {
+        self.count(|status| status == Status::Missing)
+    }
+
+    /// Returns the number of unreadable files.
+    #[must_use]
+    pub fn unreadable(&self) -> usize {
+        self.count(|status| matches!(status, Status::Unreadable { .. }))
+    }
+
+    /// Returns the number of malformed manifest lines.
+    #[must_use]
+    pub fn malformed(&self) -> usize {
+        self.malformed.len()
+    }
+
+    /// Returns whether the verification is successful.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.ok() > 0
+            && self.failed() == 0
+            && self.unreadable() == 0
+            && (self.ignore_missing || self.missing() == 0)
+            && (!self.strict || self.malformed() == 0)
+    }
+
+    fn count(&self, predicate: impl Fn(Status) -> bool) -> usize {
+        self.entries.iter().filter(|entry| predicate(entry.status)).count()
+    }
+}
+
+/// An error type for the manifest parsing.
+#[derive(Debug, thiserror::Error)]
+pub enum ManifestError {
+    /// The I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The line is not a valid manifest line, lines are numbered from one.
+    #[error("malformed manifest line `{line}`")]
+    Malformed { line: usize },
+    /// The tagged line uses an algorithm other than `SHA512`, lines are numbered from one.
+    #[error("unsupported algorithm `{algorithm}` at manifest line `{line}`")]
+    UnsupportedAlgorithm { line: usize, algorithm: String },
+}
+
+/// An error of a single line parsing, without the line number.
+enum LineError {
+    Malformed,
+    UnsupportedAlgorithm(String),
+}
+
+/// Checks whether the encoded path contains a backslash, a line feed or a carriage return, which are escaped in manifest lines.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512::manifest;
+///
+/// assert!(manifest::needs_escaping(b"new\nline"));
+/// assert!(!manifest::needs_escaping(b"plain.txt"));
+/// ```
+#[must_use]
+pub fn needs_escaping(path: &[u8]) -> bool {
+    path.iter().any(|byte| matches!(byte, b'\\' | b'\n' | b'\r'))
+}
+
+/// Escapes backslashes, line feeds and carriage returns in the encoded path as described in the [module documentation](self).
+///
+/// The leading backslash marking an escaped line is not included.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512::manifest;
+///
+/// assert_eq!(manifest::escape(b"a\\b\n"), b"a\\\\b\\n");
+/// ```
+#[must_use]
+pub fn escape(path: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(path.len());
+    for &byte in path {
+        match byte {
+            b'\\' => escaped.extend_from_slice(b"\\\\"),
+            b'\n' => escaped.extend_from_slice(b"\\n"),
+            b'\r' => escaped.extend_from_slice(b"\\r"),
+            byte => escaped.push(byte),
+        }
+    }
+    escaped
+}
+
+fn unescape(path: &[u8]) -> Option<Vec<u8>> {
+    let mut unescaped = Vec::with_capacity(path.len());
+    let mut bytes = path.iter();
+    while let Some(&byte) = bytes.next() {
+        if byte != b'\\' {
+            unescaped.push(byte);
+            continue;
+        }
+        match bytes.next()? {
+            b'\\' => unescaped.push(b'\\'),
+            b'n' => unescaped.push(b'\n'),
+            b'r' => unescaped.push(b'\r'),
+            _ => return None,
+        }
+    }
+    Some(unescaped)
+}
+
+fn encode(path: &Path) -> Vec<u8> {
+    path.as_os_str().as_encoded_bytes().to_vec()
+}
+
+#[cfg(unix)]
+fn decode(bytes: Vec<u8>) -> Option<PathBuf> {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt as _;
+
+    Some(PathBuf::from(OsString::from_vec(bytes)))
+}
+
+#[cfg(not(unix))]
+fn decode(bytes: Vec<u8>) -> Option<PathBuf> {
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
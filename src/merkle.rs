@@ -0,0 +1,373 @@
+//! This module provides Merkle tree hashing with inclusion proofs.
+//!
+//! The input is split into leaves of a fixed length, the last leaf may be shorter. The tree follows the structure of [RFC 6962](https://www.rfc-editor.org/rfc/rfc6962#section-2.1) with domain-separated hashes:
+//!
+//! * a leaf hash is the SHA-2 512 digest of the byte `0x00` followed by the leaf data,
+//! * a node hash is the SHA-2 512 digest of the byte `0x01` followed by the hashes of the left and the right child.
+//!
+//! Leaf hashes are paired level by level, a node without a sibling is promoted to the next level unchanged. The root of a tree with a single leaf is the leaf hash and the root of an empty tree is the digest of empty input.
+//!
+//! An [`InclusionProof`] generated for a leaf allows verifying that leaf against the root without the other leaves.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//! use std::num::NonZeroUsize;
+//!
+//! use chksum_sha2_512 as sha2_512;
+//! use sha2_512::merkle;
+//!
+//! # fn wrapper(path: &Path) -> std::io::Result<()> {
+//! let leaf_length = NonZeroUsize::new(1024 * 1024).unwrap();
+//! let tree = merkle::chksum(File::open(path)?, leaf_length)?;
+//! println!("{}", tree.root());
+//! if let Some(_proof) = tree.proof(0) {
+//!     // send the proof along with the first leaf to a peer
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::num::NonZeroUsize;
+use std::result;
+
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::{AsyncBufReadExt as _, AsyncRead};
+
+use crate::{Digest, SHA2_512};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Builds the Merkle tree of the reader contents.
+///
+/// # Errors
+///
+/// Returns the I/O error if the reader cannot be read.
+///
+/// # Example
+///
+/// ```rust
+/// use std::num::NonZeroUsize;
+///
+/// use chksum_sha2_512::merkle;
+///
+/// # fn wrapper() -> std::io::Result<()> {
+/// let tree = merkle::chksum(&b"example data"[..], NonZeroUsize::new(4).unwrap())?;
+/// assert_eq!(tree.leaves(), 3);
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum(reader: impl Read, leaf_length: NonZeroUsize) -> io::Result<MerkleTree> {
+    let mut hasher = MerkleHasher::new(leaf_length);
+    let mut reader = BufReader::new(reader);
+    loop {
+        let buffer = reader.fill_buf()?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hasher.update(buffer);
+        reader.consume(length);
+    }
+    Ok(hasher.finalize())
+}
+
+/// Builds the Merkle tree of the reader contents.
+///
+/// # Errors
+///
+/// Returns the I/O error if the reader cannot be read.
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum(reader: impl AsyncRead + Unpin, leaf_length: NonZeroUsize) -> io::Result<MerkleTree> {
+    let mut hasher = MerkleHasher::new(leaf_length);
+    let mut reader = tokio::io::BufReader::new(reader);
+    loop {
+        let buffer = reader.fill_buf().await?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hasher.update(buffer);
+        reader.consume(length);
+    }
+    Ok(hasher.finalize())
+}
+
+/// Computes the hash of a single leaf.
+#[must_use]
+pub fn leaf_hash(data: impl AsRef<[u8]>) -> Digest {
+    let mut hash = leaf();
+    hash.update(data);
+    hash.digest()
+}
+
+/// A hasher building the Merkle tree from the input split into fixed-size leaves.
+///
+/// The hasher implements [`Write`], so it can be used as the destination of [`io::copy`].
+#[derive(Clone, Debug)]
+pub struct MerkleHasher {
+    leaf_length: NonZeroUsize,
+    leaf: SHA2_512,
+    filled: usize,
+    leaves: Vec<Digest>,
+}
+
+impl MerkleHasher {
+    /// Creates a new hasher with the given leaf length.
+    #[must_use]
+    pub fn new(leaf_length: NonZeroUsize) -> Self {
+        Self {
+            leaf_length,
+            leaf: leaf(),
+            filled: 0,
+            leaves: Vec::new(),
+        }
+    }
+
+    /// Updates the hasher with the input data.
+    pub fn update<T>(&mut self, data: T)
+    where
+        T: AsRef<[u8]>,
+    {
+        let mut data = data.as_ref();
+        while !data.is_empty() {
+            let length = data.len().min(self.leaf_length.get() - self.filled);
+            let (head, tail) = data.split_at(length);
+            self.leaf.update(head);
+            self.filled += length;
+            if self.filled == self.leaf_length.get() {
+                self.finish_leaf();
+            }
+            data = tail;
+        }
+    }
+
+    /// Builds the tree, including the trailing partial leaf.
+    #[must_use]
+    pub fn finalize(mut self) -> MerkleTree {
+        if self.filled > 0 {
+            self.finish_leaf();
+        }
+        MerkleTree::from_leaf_hashes(self.leaf_length, self.leaves)
+    }
+
+    fn finish_leaf(&mut self) {
+        self.leaves.push(self.leaf.digest());
+        self.leaf = leaf();
+        self.filled = 0;
+    }
+}
+
+impl Write for MerkleHasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A Merkle tree over fixed-size leaves.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerkleTree {
+    leaf_length: NonZeroUsize,
+    /// Hashes of all levels, from the leaves up to the root.
+    levels: Vec<Vec<Digest>>,
+}
+
+impl MerkleTree {
+    fn from_leaf_hashes(leaf_length: NonZeroUsize, leaves: Vec<Digest>) -> Self {
+        let mut levels = vec![leaves];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next = level
+                .chunks(2)
+                .map(|pair| {
+                    match pair {
+                        [left, right] => node_hash(left, right),
+                        [single] => single.clone(),
+                        _ => unreachable!("chunks must not be empty"),
+                    }
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self { leaf_length, levels }
+    }
+
+    /// Returns the root hash.
+    #[must_use]
+    pub fn root(&self) -> Digest {
+        match self.levels.last().and_then(|level| level.first()) {
+            Some(root) => root.clone(),
+            None => SHA2_512::new().digest(),
+        }
+    }
+
+    /// Returns the leaf length.
+    #[must_use]
+    pub const fn leaf_length(&self) -> NonZeroUsize {
+        self.leaf_length
+    }
+
+    /// Returns the number of leaves.
+    #[must_use]
+    pub fn leaves(&self) -> u64 {
+        self.levels[0].len() as u64
+    }
+
+    /// Returns the hashes of the leaves.
+    #[must_use]
+    pub fn leaf_hashes(&self) -> &[Digest] {
+        &self.levels[0]
+    }
+
+    /// Generates the inclusion proof of the leaf with the given index, or [`None`] if there is no such leaf.
+    #[must_use]
+    pub fn proof(&self, index: u64) -> Option<InclusionProof> {
+        let leaves = self.leaves();
+        if index >= leaves {
+            return None;
+        }
+        let mut path = Vec::new();
+        let mut position = index as usize;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                path.push(sibling.clone());
+            }
+            position /= 2;
+        }
+        Some(InclusionProof { index, leaves, path })
+    }
+}
+
+/// A proof of inclusion of a single leaf in a [`MerkleTree`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InclusionProof {
+    index: u64,
+    leaves: u64,
+    path: Vec<Digest>,
+}
+
+impl InclusionProof {
+    /// Creates a new proof from its parts, for example received from a peer.
+    #[must_use]
+    pub fn new(index: u64, leaves: u64, path: Vec<Digest>) -> Self {
+        Self { index, leaves, path }
+    }
+
+    /// Returns the index of the leaf.
+    #[must_use]
+    pub const fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Returns the number of leaves of the tree.
+    #[must_use]
+    pub const fn leaves(&self) -> u64 {
+        self.leaves
+    }
+
+    /// Returns the sibling hashes from the leaf level up to the root.
+    #[must_use]
+    pub fn path(&self) -> &[Digest] {
+        &self.path
+    }
+
+    /// Verifies the leaf data against the root hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProofError`] if the proof is malformed or the computed root differs from the given one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chksum_sha2_512::merkle;
+    ///
+    /// # fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tree = merkle::chksum(&b"example data"[..], NonZeroUsize::new(4).unwrap())?;
+    /// let proof = tree.proof(1).unwrap();
+    /// proof.verify(b"ple ", &tree.root())?;
+    /// assert!(proof.verify(b"data", &tree.root()).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify(&self, leaf: impl AsRef<[u8]>, root: &Digest) -> result::Result<(), ProofError> {
+        self.verify_hash(&leaf_hash(leaf), root)
+    }
+
+    /// Verifies the leaf hash against the root hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProofError`] if the proof is malformed or the computed root differs from the given one.
+    pub fn verify_hash(&self, leaf_hash: &Digest, root: &Digest) -> result::Result<(), ProofError> {
+        if self.index >= self.leaves {
+            let error = ProofError::InvalidIndex {
+                index: self.index,
+                leaves: self.leaves,
+            };
+            return Err(error);
+        }
+        let mut path = self.path.iter();
+        let mut hash = leaf_hash.clone();
+        let mut position = self.index;
+        let mut width = self.leaves;
+        while width > 1 {
+            if position % 2 == 1 {
+                let sibling = path.next().ok_or(ProofError::InvalidLength)?;
+                hash = node_hash(sibling, &hash);
+            } else if position + 1 < width {
+                let sibling = path.next().ok_or(ProofError::InvalidLength)?;
+                hash = node_hash(&hash, sibling);
+            }
+            position /= 2;
+            width = width.div_ceil(2);
+        }
+        if path.next().is_some() {
+            return Err(ProofError::InvalidLength);
+        }
+        if hash != *root {
+            return Err(ProofError::Mismatch);
+        }
+        Ok(())
+    }
+}
+
+/// An error type for the inclusion proof verification.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ProofError {
+    /// The leaf index is not less than the number of leaves.
+    #[error("invalid leaf index `{index}` for `{leaves}` leaves")]
+    InvalidIndex { index: u64, leaves: u64 },
+    /// The number of hashes in the path differs from the tree height at the leaf.
+    #[error("invalid proof path length")]
+    InvalidLength,
+    /// The computed root differs from the expected one.
+    #[error("root mismatch")]
+    Mismatch,
+}
+
+/// Creates a new hash with the leaf prefix already processed.
+fn leaf() -> SHA2_512 {
+    let mut hash = SHA2_512::new();
+    hash.update([LEAF_PREFIX]);
+    hash
+}
+
+/// Computes the hash of an inner node.
+fn node_hash(left: &Digest, right: &Digest) -> Digest {
+    let mut hash = SHA2_512::new();
+    hash.update([NODE_PREFIX]);
+    hash.update(left);
+    hash.update(right);
+    hash.digest()
+}
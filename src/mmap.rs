@@ -0,0 +1,121 @@
+//! This module provides hashing of files through memory mapping.
+//!
+//! Mapping a file avoids copying its contents into an intermediate buffer, which speeds up hashing of large local files.
+//!
+//! Inputs that cannot be mapped, like pipes, character devices or empty files, are hashed with buffered reading instead.
+//!
+//! # Safety
+//!
+//! Reading pages of a mapped file that was truncated by another process is undefined behavior, on Unix the process is terminated with `SIGBUS`. The mapping cannot prevent it, so [`chksum_mmap`] is unsafe and the caller must ensure that the file is not truncated while it is hashed.
+//!
+//! The file length is checked before every chunk of the mapping is processed and [`MmapError::Truncated`] is returned if the file got shorter in the meantime. This only detects truncation between chunks and does not make concurrent truncation safe.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use chksum_sha2_512 as sha2_512;
+//!
+//! # fn wrapper(path: &Path) -> Result<(), sha2_512::MmapError> {
+//! // SAFETY: The file is not modified while it is hashed.
+//! let digest = unsafe { sha2_512::chksum_mmap(path)? };
+//! assert_eq!(digest, sha2_512::chksum(path).unwrap());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::result;
+
+use memmap2::Mmap;
+
+use crate::{Digest, SHA2_512};
+
+/// The length of the mapping processed between consecutive truncation checks.
+const CHUNK_LENGTH: usize = 16 * 1024 * 1024;
+
+/// Computes the hash of the file through memory mapping.
+///
+/// Falls back to buffered reading for inputs that cannot be mapped.
+///
+/// # Errors
+///
+/// Returns [`MmapError::Io`] if the file cannot be opened or read and [`MmapError::Truncated`] if the file was truncated while mapped.
+///
+/// # Safety
+///
+/// The file must not be truncated while it is hashed, see the [module documentation](self#safety).
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha2_512 as sha2_512;
+///
+/// # fn wrapper(path: &Path) -> Result<(), sha2_512::MmapError> {
+/// // SAFETY: The file is not modified while it is hashed.
+/// let digest = unsafe { sha2_512::chksum_mmap(path)? };
+/// println!("{digest}");
+/// # Ok(())
+/// # }
+/// ```
+#[allow(unsafe_code)]
+pub unsafe fn chksum_mmap(path: impl AsRef<Path>) -> result::Result<Digest, MmapError> {
+    let file = File::open(path)?;
+    let metadata = file.metadata()?;
+    let length = metadata.len();
+    if !metadata.is_file() || length == 0 {
+        return Ok(chksum_buffered(file)?);
+    }
+
+    // SAFETY: The mapping is only read and the caller guarantees that the file is not truncated while it is mapped,
+    // otherwise reading it would access pages past the end of the file.
+    let mmap = match unsafe { Mmap::map(&file) } {
+        Ok(mmap) => mmap,
+        Err(_) => return Ok(chksum_buffered(file)?),
+    };
+    #[cfg(unix)]
+    let _ = mmap.advise(memmap2::Advice::Sequential);
+
+    let mut hash = SHA2_512::new();
+    for chunk in mmap.chunks(CHUNK_LENGTH) {
+        let actual = file.metadata()?.len();
+        if actual < length {
+            return Err(MmapError::Truncated {
+                expected: length,
+                actual,
+            });
+        }
+        hash.update(chunk);
+    }
+    Ok(hash.digest())
+}
+
+/// An error type for the memory-mapped hashing.
+#[derive(Debug, thiserror::Error)]
+pub enum MmapError {
+    /// The I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The file was truncated while mapped.
+    #[error("file truncated while mapped, expected length `{expected}`, actual length `{actual}`")]
+    Truncated { expected: u64, actual: u64 },
+}
+
+/// Computes the hash of the file contents with buffered reading.
+fn chksum_buffered(file: File) -> io::Result<Digest> {
+    let mut hash = SHA2_512::new();
+    let mut reader = BufReader::new(file);
+    loop {
+        let buffer = reader.fill_buf()?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hash.update(buffer);
+        reader.consume(length);
+    }
+    Ok(hash.digest())
+}
@@ -0,0 +1,323 @@
+//! This module provides progress reporting for long-running hashes.
+//!
+//! A [`ProgressHook`] is invoked with a [`Progress`] snapshot every time the configured number of bytes has been processed and once more when hashing finishes. Any closure accepting `&Progress` implements the trait.
+//!
+//! The hook is wrapped in a [`ProgressReporter`], which can be passed to:
+//!
+//! * [`chksum`] and [`async_chksum`] for any input,
+//! * [`chksum_file`] and [`async_chksum_file`] for files, with the total size taken from the file metadata,
+//! * [`chksum_dir_with_context`](crate::chksum_dir_with_context) and [`async_chksum_dir_with_context`](crate::async_chksum_dir_with_context) for directory trees through the [`DirContext`](crate::DirContext), with the path of the file currently being hashed,
+//! * `reader::with_progress` and `writer::with_progress` when the respective features are enabled.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use chksum_sha2_512 as sha2_512;
+//! use sha2_512::progress::{self, Progress, ProgressReporter};
+//!
+//! # fn wrapper(path: &Path) -> sha2_512::Result<()> {
+//! let reporter = ProgressReporter::new(|progress: &Progress| {
+//!     if let Some(total) = progress.total() {
+//!         println!("{} of {total} bytes", progress.processed());
+//!     }
+//! })
+//! .with_interval(64 * 1024 * 1024);
+//! let digest = progress::chksum_file(path, reporter)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Debug, Formatter};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use chksum_core as core;
+
+use crate::{Digest, Result, SHA2_512};
+
+/// The default number of bytes processed between consecutive reports.
+pub const DEFAULT_INTERVAL: u64 = 1024 * 1024;
+
+/// A snapshot of the hashing progress.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Progress<'a> {
+    processed: u64,
+    total: Option<u64>,
+    path: Option<&'a Path>,
+}
+
+impl<'a> Progress<'a> {
+    /// Returns the number of bytes processed so far.
+    #[must_use]
+    pub const fn processed(&self) -> u64 {
+        self.processed
+    }
+
+    /// Returns the total number of bytes, if known.
+    #[must_use]
+    pub const fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Returns the path of the file currently being hashed, if any.
+    #[must_use]
+    pub const fn path(&self) -> Option<&'a Path> {
+        self.path
+    }
+}
+
+/// A trait for receiving progress reports.
+///
+/// The trait is implemented for all closures accepting `&Progress`. Hooks must be [`Send`], so the same hook can be used with the asynchronous functions.
+pub trait ProgressHook: Send {
+    /// Receives a progress report.
+    fn report(&mut self, progress: &Progress<'_>);
+}
+
+impl<F> ProgressHook for F
+where
+    F: FnMut(&Progress<'_>) + Send,
+{
+    fn report(&mut self, progress: &Progress<'_>) {
+        self(progress);
+    }
+}
+
+/// Tracks the number of processed bytes and invokes the [`ProgressHook`] at the configured interval.
+///
+/// The default reporter has no hook and reports nothing.
+pub struct ProgressReporter {
+    hook: Option<Box<dyn ProgressHook>>,
+    interval: u64,
+    total: Option<u64>,
+    processed: u64,
+    reported: Option<u64>,
+    path: Option<PathBuf>,
+}
+
+impl ProgressReporter {
+    /// Creates a new reporter with the given hook and the [`DEFAULT_INTERVAL`].
+    #[must_use]
+    pub fn new(hook: impl ProgressHook + 'static) -> Self {
+        Self {
+            hook: Some(Box::new(hook)),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the number of bytes processed between consecutive reports.
+    ///
+    /// An interval of zero reports after every update.
+    #[must_use]
+    pub fn with_interval(mut self, interval: u64) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the total number of bytes.
+    ///
+    /// The total is set automatically when hashing files and directory trees.
+    #[must_use]
+    pub fn with_total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Returns the number of bytes processed between consecutive reports.
+    #[must_use]
+    pub const fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Returns the total number of bytes, if known.
+    #[must_use]
+    pub const fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Returns the number of bytes processed so far.
+    #[must_use]
+    pub const fn processed(&self) -> u64 {
+        self.processed
+    }
+
+    /// Sets the total number of bytes unless it was already given.
+    pub(crate) fn default_total(&mut self, total: u64) {
+        self.total.get_or_insert(total);
+    }
+
+    /// Sets the path of the file currently being hashed.
+    pub(crate) fn set_path(&mut self, path: Option<&Path>) {
+        self.path = path.map(Path::to_path_buf);
+    }
+
+    /// Accounts for processed bytes, reporting if the interval has elapsed.
+    pub(crate) fn advance(&mut self, length: u64) {
+        self.processed += length;
+        let reported = self.reported.unwrap_or(0);
+        if self.processed - reported >= self.interval.max(1) {
+            self.report();
+        }
+    }
+
+    /// Reports the final progress unless it was already reported.
+    pub(crate) fn finish(&mut self) {
+        if self.reported != Some(self.processed) {
+            self.report();
+        }
+    }
+
+    fn report(&mut self) {
+        self.reported = Some(self.processed);
+        if let Some(hook) = &mut self.hook {
+            hook.report(&Progress {
+                processed: self.processed,
+                total: self.total,
+                path: self.path.as_deref(),
+            });
+        }
+    }
+
+    fn reset(&mut self) {
+        self.processed = 0;
+        self.reported = None;
+        self.path = None;
+    }
+}
+
+impl Default for ProgressReporter {
+    fn default() -> Self {
+        Self {
+            hook: None,
+            interval: DEFAULT_INTERVAL,
+            total: None,
+            processed: 0,
+            reported: None,
+            path: None,
+        }
+    }
+}
+
+impl Debug for ProgressReporter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressReporter")
+            .field("interval", &self.interval)
+            .field("total", &self.total)
+            .field("processed", &self.processed)
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The [`SHA2_512`] hash instance reporting the number of processed bytes.
+///
+/// It can be used with any interface accepting a generic [`Hash`](core::Hash), like the `Reader` and `Writer` types. The final progress is reported by the functions of this module, other interfaces report only at the configured interval.
+#[derive(Debug, Default)]
+pub struct ProgressHash {
+    hash: SHA2_512,
+    reporter: ProgressReporter,
+}
+
+impl ProgressHash {
+    /// Creates a new hash with the given reporter.
+    #[must_use]
+    pub fn new(reporter: ProgressReporter) -> Self {
+        let hash = SHA2_512::new();
+        Self { hash, reporter }
+    }
+
+    /// Returns the reporter.
+    #[must_use]
+    pub const fn reporter(&self) -> &ProgressReporter {
+        &self.reporter
+    }
+
+    /// Reports the final progress and produces the hash digest.
+    fn finish(mut self) -> Digest {
+        self.reporter.finish();
+        self.hash.digest()
+    }
+}
+
+impl core::Hash for ProgressHash {
+    type Digest = Digest;
+
+    fn update<T>(&mut self, data: T)
+    where
+        T: AsRef<[u8]>,
+    {
+        let data = data.as_ref();
+        self.hash.update(data);
+        self.reporter.advance(data.len() as u64);
+    }
+
+    fn reset(&mut self) {
+        self.hash.reset();
+        self.reporter.reset();
+    }
+
+    fn digest(&self) -> Self::Digest {
+        self.hash.digest()
+    }
+}
+
+/// Computes the hash of the given input, reporting the progress.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha2_512 as sha2_512;
+/// use sha2_512::progress::{self, Progress, ProgressReporter};
+///
+/// # fn wrapper() -> sha2_512::Result<()> {
+/// let data = b"example data";
+/// let reporter =
+///     ProgressReporter::new(|progress: &Progress| println!("{}", progress.processed()));
+/// let digest = progress::chksum(data, reporter)?;
+/// assert_eq!(digest, sha2_512::hash(data));
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum(mut data: impl core::Chksumable, reporter: ProgressReporter) -> Result<Digest> {
+    let mut hash = ProgressHash::new(reporter);
+    data.chksum_with(&mut hash)?;
+    Ok(hash.finish())
+}
+
+/// Computes the hash of the given input, reporting the progress.
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum(mut data: impl core::AsyncChksumable, reporter: ProgressReporter) -> Result<Digest> {
+    let mut hash = ProgressHash::new(reporter);
+    data.chksum_with(&mut hash).await?;
+    Ok(hash.finish())
+}
+
+/// Computes the hash of the file, reporting the progress.
+///
+/// Unless already given, the total is set to the file length. The reported path is the given path.
+pub fn chksum_file(path: impl AsRef<Path>, mut reporter: ProgressReporter) -> Result<Digest> {
+    let path = path.as_ref();
+    let file = File::open(path)?;
+    let metadata = file.metadata()?;
+    if metadata.is_file() {
+        reporter.default_total(metadata.len());
+    }
+    reporter.set_path(Some(path));
+    chksum(file, reporter)
+}
+
+/// Computes the hash of the file, reporting the progress.
+///
+/// Unless already given, the total is set to the file length. The reported path is the given path.
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_file(path: impl AsRef<Path>, mut reporter: ProgressReporter) -> Result<Digest> {
+    let path = path.as_ref();
+    let file = tokio::fs::File::open(path).await?;
+    let metadata = file.metadata().await?;
+    if metadata.is_file() {
+        reporter.default_total(metadata.len());
+    }
+    reporter.set_path(Some(path));
+    async_chksum(file, reporter).await
+}
@@ -0,0 +1,117 @@
+//! This module provides hashing of byte ranges of files.
+//!
+//! Only the requested slice is read, so segments of large files can be verified independently.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! use chksum_sha2_512 as sha2_512;
+//!
+//! # fn wrapper(path: &Path) -> Result<(), sha2_512::RangeError> {
+//! let file = File::open(path)?;
+//! let digest = sha2_512::chksum_range(file, 1024, 4096)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::result;
+
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::{AsyncBufReadExt as _, AsyncRead, AsyncReadExt as _, AsyncSeek, AsyncSeekExt as _};
+
+use crate::{Digest, SHA2_512};
+
+/// Computes the hash of `length` bytes of the file starting at `offset`.
+///
+/// # Errors
+///
+/// Returns [`RangeError::OutOfRange`] if the file ends before the end of the range and [`RangeError::Io`] if the file cannot be read.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Cursor;
+///
+/// use chksum_sha2_512 as sha2_512;
+///
+/// # fn wrapper() -> Result<(), sha2_512::RangeError> {
+/// let file = Cursor::new(b"some example data");
+/// let digest = sha2_512::chksum_range(file, 5, 12)?;
+/// assert_eq!(digest, sha2_512::hash(b"example data"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_range(mut file: impl Read + Seek, offset: u64, length: u64) -> result::Result<Digest, RangeError> {
+    file.seek(SeekFrom::Start(offset))?;
+    let mut hash = SHA2_512::new();
+    let mut reader = BufReader::new((&mut file).take(length));
+    let mut processed = 0;
+    loop {
+        let buffer = reader.fill_buf()?;
+        let size = buffer.len();
+        if size == 0 {
+            break;
+        }
+        hash.update(buffer);
+        reader.consume(size);
+        processed += size as u64;
+    }
+    drop(reader);
+    if processed < length {
+        return Err(RangeError::OutOfRange {
+            end: offset.saturating_add(length),
+            length: file.seek(SeekFrom::End(0))?,
+        });
+    }
+    Ok(hash.digest())
+}
+
+/// Computes the hash of `length` bytes of the file starting at `offset`.
+///
+/// # Errors
+///
+/// Returns [`RangeError::OutOfRange`] if the file ends before the end of the range and [`RangeError::Io`] if the file cannot be read.
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_range(
+    mut file: impl AsyncRead + AsyncSeek + Unpin,
+    offset: u64,
+    length: u64,
+) -> result::Result<Digest, RangeError> {
+    file.seek(SeekFrom::Start(offset)).await?;
+    let mut hash = SHA2_512::new();
+    let mut reader = tokio::io::BufReader::new((&mut file).take(length));
+    let mut processed = 0;
+    loop {
+        let buffer = reader.fill_buf().await?;
+        let size = buffer.len();
+        if size == 0 {
+            break;
+        }
+        hash.update(buffer);
+        reader.consume(size);
+        processed += size as u64;
+    }
+    drop(reader);
+    if processed < length {
+        return Err(RangeError::OutOfRange {
+            end: offset.saturating_add(length),
+            length: file.seek(SeekFrom::End(0)).await?,
+        });
+    }
+    Ok(hash.digest())
+}
+
+/// An error type for the range hashing.
+#[derive(Debug, thiserror::Error)]
+pub enum RangeError {
+    /// The I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The file is shorter than the end of the requested range.
+    #[error("range end `{end}` exceeds file length `{length}`")]
+    OutOfRange { end: u64, length: u64 },
+}
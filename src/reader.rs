@@ -44,21 +44,35 @@
 //! # }
 //! ```
 
-use std::io::Read;
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+#[cfg(feature = "async-runtime-tokio")]
+use std::pin::Pin;
+#[cfg(feature = "async-runtime-tokio")]
+use std::task::{Context, Poll};
 
 use chksum_reader as reader;
 #[cfg(feature = "async-runtime-tokio")]
-use tokio::io::AsyncRead;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt as _, ReadBuf};
 
-use crate::SHA2_512;
+use crate::progress::{ProgressHash, ProgressReporter};
+use crate::{Digest, SHA2_512};
 
 /// A specialized [`Reader`](reader::Reader) type with the [`SHA2_512`] hash algorithm.
+///
+/// Data consumed through [`BufRead`] is not hashed, use [`BufferedReader`] instead to hash lines read with [`BufRead::read_line`] or [`BufRead::lines`].
 pub type Reader<R> = reader::Reader<R, SHA2_512>;
 
 #[cfg(feature = "async-runtime-tokio")]
 /// A specialized [`AsyncReader`](reader::AsyncReader) type with the [`SHA2_512`] hash algorithm.
 pub type AsyncReader<R> = reader::AsyncReader<R, SHA2_512>;
 
+/// A specialized [`Reader`](reader::Reader) type with the [`ProgressHash`] hash reporting the progress.
+pub type ProgressReader<R> = reader::Reader<R, ProgressHash>;
+
+#[cfg(feature = "async-runtime-tokio")]
+/// A specialized [`AsyncReader`](reader::AsyncReader) type with the [`ProgressHash`] hash reporting the progress.
+pub type AsyncProgressReader<R> = reader::AsyncReader<R, ProgressHash>;
+
 /// Creates new [`Reader`].
 pub fn new(inner: impl Read) -> Reader<impl Read> {
     reader::new(inner)
@@ -69,6 +83,41 @@ pub fn with_hash(inner: impl Read, hash: SHA2_512) -> Reader<impl Read> {
     reader::with_hash(inner, hash)
 }
 
+/// Creates new [`ProgressReader`] reporting the progress to the provided reporter.
+pub fn with_progress(inner: impl Read, reporter: ProgressReporter) -> ProgressReader<impl Read> {
+    reader::with_hash(inner, ProgressHash::new(reporter))
+}
+
+/// Creates new [`LimitedReader`] with provided hash, failing once more than `limit` bytes are read.
+pub fn with_limit(inner: impl Read, hash: SHA2_512, limit: u64) -> LimitedReader<impl Read> {
+    LimitedReader::new(inner, hash, limit)
+}
+
+/// Creates new [`BufferedReader`] hashing the consumed bytes.
+pub fn buffered<R>(inner: R) -> BufferedReader<R>
+where
+    R: BufRead,
+{
+    BufferedReader::new(inner)
+}
+
+/// Creates new [`SeekingReader`] handling backward seeks according to the mode.
+pub fn seekable<R>(inner: R, mode: SeekMode) -> SeekingReader<R>
+where
+    R: Read + Seek,
+{
+    SeekingReader::new(inner, mode)
+}
+
+/// Creates new [`TeeReader`] forwarding all read bytes to the sink.
+pub fn tee<R, W>(inner: R, sink: W) -> TeeReader<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    TeeReader::new(inner, sink)
+}
+
 #[cfg(feature = "async-runtime-tokio")]
 /// Creates new [`AsyncReader`].
 pub fn async_new(inner: impl AsyncRead) -> AsyncReader<impl AsyncRead> {
@@ -80,3 +129,896 @@ pub fn async_new(inner: impl AsyncRead) -> AsyncReader<impl AsyncRead> {
 pub fn async_with_hash(inner: impl AsyncRead, hash: SHA2_512) -> AsyncReader<impl AsyncRead> {
     reader::async_with_hash(inner, hash)
 }
+
+#[cfg(feature = "async-runtime-tokio")]
+/// Creates new [`AsyncProgressReader`] reporting the progress to the provided reporter.
+pub fn async_with_progress(inner: impl AsyncRead, reporter: ProgressReporter) -> AsyncProgressReader<impl AsyncRead> {
+    reader::async_with_hash(inner, ProgressHash::new(reporter))
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+/// Creates new [`AsyncLimitedReader`] with provided hash, failing once more than `limit` bytes are read.
+pub fn async_with_limit(
+    inner: impl AsyncRead + Unpin,
+    hash: SHA2_512,
+    limit: u64,
+) -> AsyncLimitedReader<impl AsyncRead + Unpin> {
+    AsyncLimitedReader::new(inner, hash, limit)
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+/// Creates new [`AsyncTeeReader`] forwarding all read bytes to the sink.
+pub fn async_tee<R, W>(inner: R, sink: W) -> AsyncTeeReader<R, W>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    AsyncTeeReader::new(inner, sink)
+}
+
+/// A reader hashing the read data and writing it to a sink at the same time.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::{self, Read};
+///
+/// use chksum_sha2_512 as sha2_512;
+/// use sha2_512::reader;
+///
+/// # fn wrapper() -> io::Result<()> {
+/// let data = b"example data";
+/// let mut reader = reader::tee(&data[..], Vec::new());
+/// io::copy(&mut reader, &mut io::sink())?;
+/// let (digest, sink) = reader.finish()?;
+/// assert_eq!(digest, sha2_512::hash(data));
+/// assert_eq!(sink, data);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TeeReader<R, W> {
+    inner: R,
+    sink: W,
+    hash: SHA2_512,
+}
+
+impl<R, W> TeeReader<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Creates new [`TeeReader`] forwarding all read bytes to the sink.
+    pub fn new(inner: R, sink: W) -> Self {
+        let hash = SHA2_512::new();
+        Self { inner, sink, hash }
+    }
+
+    /// Returns the digest of the bytes read so far.
+    pub fn digest(&self) -> Digest {
+        self.hash.digest()
+    }
+
+    /// Flushes the sink and returns the digest of the read bytes along with the sink.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the sink cannot be flushed.
+    pub fn finish(mut self) -> io::Result<(Digest, W)> {
+        self.sink.flush()?;
+        Ok((self.hash.digest(), self.sink))
+    }
+}
+
+impl<R, W> Read for TeeReader<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = self.inner.read(buf)?;
+        let data = &buf[..length];
+        self.sink.write_all(data)?;
+        self.hash.update(data);
+        Ok(length)
+    }
+}
+
+/// A reader hashing the read data and writing it to a sink at the same time.
+///
+/// The asynchronous counterpart of [`TeeReader`]. Bytes not yet accepted by the sink are buffered and written before the next read, so the sink applies backpressure to the reader. Call [`AsyncTeeReader::finish`] to write the remaining bytes.
+#[cfg(feature = "async-runtime-tokio")]
+#[derive(Debug)]
+pub struct AsyncTeeReader<R, W> {
+    inner: R,
+    sink: W,
+    hash: SHA2_512,
+    pending: Vec<u8>,
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+impl<R, W> AsyncTeeReader<R, W>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    /// Creates new [`AsyncTeeReader`] forwarding all read bytes to the sink.
+    pub fn new(inner: R, sink: W) -> Self {
+        let hash = SHA2_512::new();
+        let pending = Vec::new();
+        Self {
+            inner,
+            sink,
+            hash,
+            pending,
+        }
+    }
+
+    /// Returns the digest of the bytes read so far.
+    pub fn digest(&self) -> Digest {
+        self.hash.digest()
+    }
+
+    /// Writes the buffered bytes, flushes the sink and returns the digest of the read bytes along with the sink.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the sink cannot be written or flushed.
+    pub async fn finish(mut self) -> io::Result<(Digest, W)> {
+        self.sink.write_all(&self.pending).await?;
+        self.sink.flush().await?;
+        Ok((self.hash.digest(), self.sink))
+    }
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+impl<R, W> AsyncRead for AsyncTeeReader<R, W>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let Self {
+            inner,
+            sink,
+            hash,
+            pending,
+        } = self.get_mut();
+        while !pending.is_empty() {
+            match Pin::new(&mut *sink).poll_write(cx, pending) {
+                Poll::Ready(Ok(0)) => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+                Poll::Ready(Ok(length)) => {
+                    pending.drain(..length);
+                },
+                Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        let filled = buf.filled().len();
+        match Pin::new(inner).poll_read(cx, buf) {
+            Poll::Ready(Ok(())) => {
+                let data = &buf.filled()[filled..];
+                hash.update(data);
+                pending.extend_from_slice(data);
+                Poll::Ready(Ok(()))
+            },
+            poll => poll,
+        }
+    }
+}
+
+/// A buffered reader hashing exactly the consumed bytes.
+///
+/// Unlike [`Reader`], the bytes are hashed when they are consumed, so both [`Read`] and [`BufRead`] methods, such as [`BufRead::read_line`] or [`BufRead::lines`], contribute to the digest.
+///
+/// Since [`BufRead::consume`] cannot fail, an error of the inner reader while consuming leaves the bytes unconsumed and unhashed and is returned by the next read.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::{self, BufRead};
+///
+/// use chksum_sha2_512 as sha2_512;
+/// use sha2_512::reader;
+///
+/// # fn wrapper() -> io::Result<()> {
+/// let data = b"first line\nsecond line\n";
+/// let mut reader = reader::buffered(&data[..]);
+/// let mut line = String::new();
+/// reader.read_line(&mut line)?;
+/// assert_eq!(line, "first line\n");
+/// assert_eq!(reader.digest(), sha2_512::hash(b"first line\n"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BufferedReader<R> {
+    inner: R,
+    hash: SHA2_512,
+    /// The length of the buffer returned by the last [`BufRead::fill_buf`] call not consumed yet.
+    filled: usize,
+    /// The error of accessing the buffer in [`BufRead::consume`], returned by the next read.
+    error: Option<io::Error>,
+}
+
+impl<R> BufferedReader<R>
+where
+    R: BufRead,
+{
+    /// Creates new [`BufferedReader`].
+    pub fn new(inner: R) -> Self {
+        Self::with_hash(inner, SHA2_512::new())
+    }
+
+    /// Creates new [`BufferedReader`] with provided hash.
+    pub fn with_hash(inner: R, hash: SHA2_512) -> Self {
+        Self {
+            inner,
+            hash,
+            filled: 0,
+            error: None,
+        }
+    }
+
+    /// Returns the digest of the bytes consumed so far.
+    pub fn digest(&self) -> Digest {
+        self.hash.digest()
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> Read for BufferedReader<R>
+where
+    R: BufRead,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        let data = self.inner.fill_buf()?;
+        let length = data.len().min(buf.len());
+        buf[..length].copy_from_slice(&data[..length]);
+        self.hash.update(&data[..length]);
+        self.inner.consume(length);
+        self.filled = 0;
+        Ok(length)
+    }
+}
+
+impl<R> BufRead for BufferedReader<R>
+where
+    R: BufRead,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        let data = self.inner.fill_buf()?;
+        self.filled = data.len();
+        Ok(data)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.filled);
+        if amt == 0 {
+            return;
+        }
+        // The buffer was already filled, so this only borrows it again without reading from the underlying source.
+        match self.inner.fill_buf() {
+            Ok(data) => {
+                let amt = amt.min(data.len());
+                self.hash.update(&data[..amt]);
+                self.inner.consume(amt);
+                self.filled -= amt;
+            },
+            Err(error) => self.error = Some(error),
+        }
+    }
+}
+
+/// The behavior of [`SeekingReader`] on seeks before the current position.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SeekMode {
+    /// Fails with an [`io::Error`] of the [`InvalidInput`](io::ErrorKind::InvalidInput) kind, wrapping a [`BackwardSeek`].
+    Reject,
+    /// Restarts hashing from the start of the inner reader.
+    Rehash,
+}
+
+/// A reader supporting seeks while keeping the digest consistent with the position.
+///
+/// The digest always covers exactly the bytes from the start of the inner reader up to the current position. Seeking forward reads and hashes the skipped bytes, seeking past the end fails with an [`io::Error`] of the [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) kind. Seeking backward is handled according to the [`SeekMode`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::{self, Cursor, Read, Seek, SeekFrom};
+///
+/// use chksum_sha2_512 as sha2_512;
+/// use sha2_512::reader::{self, BackwardSeek, SeekMode};
+///
+/// # fn wrapper() -> io::Result<()> {
+/// let data = b"example data";
+/// let mut reader = reader::seekable(Cursor::new(data), SeekMode::Reject);
+/// reader.seek(SeekFrom::Start(8))?;
+/// assert_eq!(reader.digest(), sha2_512::hash(b"example "));
+///
+/// let error = reader.seek(SeekFrom::Start(0)).unwrap_err();
+/// assert!(BackwardSeek::from_io(&error).is_some());
+///
+/// let mut reader = reader::seekable(Cursor::new(data), SeekMode::Rehash);
+/// io::copy(&mut reader, &mut io::sink())?;
+/// reader.seek(SeekFrom::Start(7))?;
+/// assert_eq!(reader.digest(), sha2_512::hash(b"example"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SeekingReader<R> {
+    inner: R,
+    hash: SHA2_512,
+    mode: SeekMode,
+    position: u64,
+}
+
+impl<R> SeekingReader<R>
+where
+    R: Read + Seek,
+{
+    /// Creates new [`SeekingReader`] with provided mode.
+    ///
+    /// The inner reader is expected to be positioned at its start.
+    pub fn new(inner: R, mode: SeekMode) -> Self {
+        let hash = SHA2_512::new();
+        Self {
+            inner,
+            hash,
+            mode,
+            position: 0,
+        }
+    }
+
+    /// Returns the mode.
+    pub fn mode(&self) -> SeekMode {
+        self.mode
+    }
+
+    /// Returns the current position, which is also the number of hashed bytes.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the digest of the bytes from the start up to the current position.
+    pub fn digest(&self) -> Digest {
+        self.hash.digest()
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> Read for SeekingReader<R>
+where
+    R: Read + Seek,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = self.inner.read(buf)?;
+        self.hash.update(&buf[..length]);
+        self.position += length as u64;
+        Ok(length)
+    }
+}
+
+impl<R> Seek for SeekingReader<R>
+where
+    R: Read + Seek,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+            SeekFrom::End(offset) => {
+                let target = self.inner.seek(SeekFrom::End(offset))?;
+                self.inner.seek(SeekFrom::Start(self.position))?;
+                Some(target)
+            },
+        };
+        let target = target.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        if target < self.position {
+            match self.mode {
+                SeekMode::Reject => {
+                    let error = BackwardSeek {
+                        position: self.position,
+                        target,
+                    };
+                    return Err(error.into());
+                },
+                SeekMode::Rehash => {
+                    self.inner.seek(SeekFrom::Start(0))?;
+                    self.hash = SHA2_512::new();
+                    self.position = 0;
+                },
+            }
+        }
+        let remaining = target - self.position;
+        io::copy(&mut self.by_ref().take(remaining), &mut io::sink())?;
+        if self.position < target {
+            let error = io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "cannot seek past the end of the hashed data",
+            );
+            return Err(error);
+        }
+        Ok(self.position)
+    }
+}
+
+/// A reader hashing at most a limited number of bytes.
+///
+/// Once the inner reader provides more data than the limit allows, the bytes up to the limit are returned and the following `read` returns an [`io::Error`] of the [`InvalidData`](io::ErrorKind::InvalidData) kind, wrapping a [`LimitExceeded`]. Bytes past the limit are neither returned nor hashed.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::{self, Read};
+///
+/// use chksum_sha2_512 as sha2_512;
+/// use sha2_512::reader::{self, LimitExceeded};
+///
+/// let data = b"example data";
+/// let mut reader = reader::with_limit(&data[..], sha2_512::new(), 7);
+/// let mut buffer = Vec::new();
+/// let error = reader.read_to_end(&mut buffer).unwrap_err();
+/// assert!(LimitExceeded::from_io(&error).is_some());
+/// assert_eq!(buffer, b"example");
+/// assert_eq!(reader.bytes_read(), 7);
+/// assert_eq!(reader.digest(), sha2_512::hash(b"example"));
+/// ```
+#[derive(Debug)]
+pub struct LimitedReader<R> {
+    inner: R,
+    limiter: Limiter,
+}
+
+impl<R> LimitedReader<R>
+where
+    R: Read,
+{
+    /// Creates new [`LimitedReader`] with provided hash and limit.
+    pub fn new(inner: R, hash: SHA2_512, limit: u64) -> Self {
+        let limiter = Limiter::new(hash, limit);
+        Self { inner, limiter }
+    }
+
+    /// Returns the number of bytes read and hashed so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.limiter.bytes_read
+    }
+
+    /// Returns the limit.
+    pub fn limit(&self) -> u64 {
+        self.limiter.limit
+    }
+
+    /// Returns the digest of the bytes read so far.
+    pub fn digest(&self) -> Digest {
+        self.limiter.hash.digest()
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> Read for LimitedReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.limiter.check()?;
+        let length = self.inner.read(buf)?;
+        self.limiter.update(&buf[..length])
+    }
+}
+
+/// A reader hashing at most a limited number of bytes.
+///
+/// The asynchronous counterpart of [`LimitedReader`].
+#[cfg(feature = "async-runtime-tokio")]
+#[derive(Debug)]
+pub struct AsyncLimitedReader<R> {
+    inner: R,
+    limiter: Limiter,
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+impl<R> AsyncLimitedReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Creates new [`AsyncLimitedReader`] with provided hash and limit.
+    pub fn new(inner: R, hash: SHA2_512, limit: u64) -> Self {
+        let limiter = Limiter::new(hash, limit);
+        Self { inner, limiter }
+    }
+
+    /// Returns the number of bytes read and hashed so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.limiter.bytes_read
+    }
+
+    /// Returns the limit.
+    pub fn limit(&self) -> u64 {
+        self.limiter.limit
+    }
+
+    /// Returns the digest of the bytes read so far.
+    pub fn digest(&self) -> Digest {
+        self.limiter.hash.digest()
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+impl<R> AsyncRead for AsyncLimitedReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let Self { inner, limiter } = self.get_mut();
+        limiter.check()?;
+        let filled = buf.filled().len();
+        match Pin::new(inner).poll_read(cx, buf) {
+            Poll::Ready(Ok(())) => {
+                let result = limiter.update(&buf.filled()[filled..]);
+                // Bytes past the limit are dropped, nothing may be reported as read alongside an error.
+                buf.set_filled(filled + result.as_ref().map_or(0, |length| *length));
+                Poll::Ready(result.map(|_| ()))
+            },
+            poll => poll,
+        }
+    }
+}
+
+/// A reader verifying the digest and optionally the length of the read data.
+///
+/// The data is passed through unchanged. When the inner reader reaches its end, the final `read` returns an [`io::Error`] of the [`InvalidData`](io::ErrorKind::InvalidData) kind, wrapping a [`VerificationError`], if the digest or the length does not match. An expected length is also enforced while reading, so the error is returned as soon as the data turns out to be longer.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::{self, ErrorKind};
+///
+/// use chksum_sha2_512 as sha2_512;
+/// use sha2_512::reader::{VerificationError, VerifyingReader};
+///
+/// let expected = sha2_512::hash(b"example data");
+/// let mut reader = VerifyingReader::new(&b"tampered data"[..], expected);
+/// let error = io::copy(&mut reader, &mut io::sink()).unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::InvalidData);
+/// assert!(matches!(
+///     VerificationError::from_io(&error),
+///     Some(VerificationError::Mismatch { .. })
+/// ));
+/// ```
+#[derive(Debug)]
+pub struct VerifyingReader<R> {
+    inner: R,
+    verifier: Verifier,
+}
+
+impl<R> VerifyingReader<R>
+where
+    R: Read,
+{
+    /// Creates new [`VerifyingReader`] with the expected digest.
+    pub fn new(inner: R, expected: Digest) -> Self {
+        let verifier = Verifier::new(expected);
+        Self { inner, verifier }
+    }
+
+    /// Sets the expected length of the data.
+    #[must_use]
+    pub fn with_length(mut self, length: u64) -> Self {
+        self.verifier.length = Some(length);
+        self
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> Read for VerifyingReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = self.inner.read(buf)?;
+        if length == 0 && !buf.is_empty() {
+            self.verifier.finish()?;
+        } else {
+            self.verifier.update(&buf[..length])?;
+        }
+        Ok(length)
+    }
+}
+
+/// A reader verifying the digest and optionally the length of the read data.
+///
+/// The asynchronous counterpart of [`VerifyingReader`].
+#[cfg(feature = "async-runtime-tokio")]
+#[derive(Debug)]
+pub struct AsyncVerifyingReader<R> {
+    inner: R,
+    verifier: Verifier,
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+impl<R> AsyncVerifyingReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Creates new [`AsyncVerifyingReader`] with the expected digest.
+    pub fn new(inner: R, expected: Digest) -> Self {
+        let verifier = Verifier::new(expected);
+        Self { inner, verifier }
+    }
+
+    /// Sets the expected length of the data.
+    #[must_use]
+    pub fn with_length(mut self, length: u64) -> Self {
+        self.verifier.length = Some(length);
+        self
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+impl<R> AsyncRead for AsyncVerifyingReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let Self { inner, verifier } = self.get_mut();
+        let filled = buf.filled().len();
+        let remaining = buf.remaining();
+        match Pin::new(inner).poll_read(cx, buf) {
+            Poll::Ready(Ok(())) => {
+                let data = &buf.filled()[filled..];
+                let result = if data.is_empty() && remaining > 0 {
+                    verifier.finish()
+                } else {
+                    verifier.update(data)
+                };
+                if result.is_err() {
+                    // Nothing may be reported as read alongside an error.
+                    buf.set_filled(filled);
+                }
+                Poll::Ready(result)
+            },
+            poll => poll,
+        }
+    }
+}
+
+/// An error type for the [`VerifyingReader`] verification.
+///
+/// The error is wrapped in an [`io::Error`] of the [`InvalidData`](io::ErrorKind::InvalidData) kind, use [`VerificationError::from_io`] to retrieve it.
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum VerificationError {
+    /// The digest of the data differs from the expected one.
+    #[error("digest mismatch, expected `{expected}`, actual `{actual}`")]
+    Mismatch { expected: Box<Digest>, actual: Box<Digest> },
+    /// The length of the data differs from the expected one. If the data is longer, the actual length is the length read before the mismatch was detected.
+    #[error("length mismatch, expected `{expected}`, actual `{actual}`")]
+    LengthMismatch { expected: u64, actual: u64 },
+}
+
+impl VerificationError {
+    /// Returns the verification error wrapped in the I/O error, if any.
+    #[must_use]
+    pub fn from_io(error: &io::Error) -> Option<&Self> {
+        error.get_ref().and_then(|error| error.downcast_ref())
+    }
+}
+
+impl From<VerificationError> for io::Error {
+    fn from(error: VerificationError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+/// An error type for the [`LimitedReader`] limit.
+///
+/// The error is wrapped in an [`io::Error`] of the [`InvalidData`](io::ErrorKind::InvalidData) kind, use [`LimitExceeded::from_io`] to retrieve it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("read limit of `{limit}` bytes exceeded")]
+pub struct LimitExceeded {
+    limit: u64,
+}
+
+impl LimitExceeded {
+    /// Returns the exceeded limit.
+    #[must_use]
+    pub const fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Returns the limit error wrapped in the I/O error, if any.
+    #[must_use]
+    pub fn from_io(error: &io::Error) -> Option<&Self> {
+        error.get_ref().and_then(|error| error.downcast_ref())
+    }
+}
+
+impl From<LimitExceeded> for io::Error {
+    fn from(error: LimitExceeded) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+/// An error type for the [`SeekingReader`] rejecting a backward seek.
+///
+/// The error is wrapped in an [`io::Error`] of the [`InvalidInput`](io::ErrorKind::InvalidInput) kind, use [`BackwardSeek::from_io`] to retrieve it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("cannot seek backward from `{position}` to `{target}` while hashing")]
+pub struct BackwardSeek {
+    position: u64,
+    target: u64,
+}
+
+impl BackwardSeek {
+    /// Returns the position of the reader when the seek was requested.
+    #[must_use]
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the requested position.
+    #[must_use]
+    pub const fn target(&self) -> u64 {
+        self.target
+    }
+
+    /// Returns the backward seek error wrapped in the I/O error, if any.
+    #[must_use]
+    pub fn from_io(error: &io::Error) -> Option<&Self> {
+        error.get_ref().and_then(|error| error.downcast_ref())
+    }
+}
+
+impl From<BackwardSeek> for io::Error {
+    fn from(error: BackwardSeek) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, error)
+    }
+}
+
+/// The limit state shared by the limited readers.
+#[derive(Debug)]
+struct Limiter {
+    hash: SHA2_512,
+    limit: u64,
+    bytes_read: u64,
+    exceeded: bool,
+}
+
+impl Limiter {
+    fn new(hash: SHA2_512, limit: u64) -> Self {
+        Self {
+            hash,
+            limit,
+            bytes_read: 0,
+            exceeded: false,
+        }
+    }
+
+    /// Fails if the limit was already exceeded.
+    fn check(&self) -> io::Result<()> {
+        if self.exceeded {
+            Err(LimitExceeded { limit: self.limit }.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Hashes the read data up to the limit, returning the number of bytes to pass through.
+    fn update(&mut self, data: &[u8]) -> io::Result<usize> {
+        let remaining = self.limit - self.bytes_read;
+        let length = match usize::try_from(remaining) {
+            Ok(remaining) if remaining < data.len() => {
+                self.exceeded = true;
+                remaining
+            },
+            _ => data.len(),
+        };
+        self.hash.update(&data[..length]);
+        self.bytes_read += length as u64;
+        if length == 0 && self.exceeded {
+            self.check()?;
+        }
+        Ok(length)
+    }
+}
+
+/// The verification state shared by the verifying readers.
+#[derive(Debug)]
+struct Verifier {
+    hash: SHA2_512,
+    expected: Digest,
+    length: Option<u64>,
+    processed: u64,
+    result: Option<Result<(), VerificationError>>,
+}
+
+impl Verifier {
+    fn new(expected: Digest) -> Self {
+        Self {
+            hash: SHA2_512::new(),
+            expected,
+            length: None,
+            processed: 0,
+            result: None,
+        }
+    }
+
+    /// Processes the read data, failing as soon as it exceeds the expected length.
+    fn update(&mut self, data: &[u8]) -> io::Result<()> {
+        self.hash.update(data);
+        self.processed += data.len() as u64;
+        match self.length {
+            Some(expected) if self.processed > expected => {
+                let error = VerificationError::LengthMismatch {
+                    expected,
+                    actual: self.processed,
+                };
+                self.result = Some(Err(error.clone()));
+                Err(error.into())
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Verifies the data at the end of the inner reader, repeating the outcome on subsequent calls.
+    fn finish(&mut self) -> io::Result<()> {
+        let result = self.result.get_or_insert_with(|| {
+            if let Some(expected) = self.length.filter(|&expected| expected != self.processed) {
+                return Err(VerificationError::LengthMismatch {
+                    expected,
+                    actual: self.processed,
+                });
+            }
+            let actual = self.hash.digest();
+            if actual == self.expected {
+                Ok(())
+            } else {
+                Err(VerificationError::Mismatch {
+                    expected: Box::new(self.expected.clone()),
+                    actual: Box::new(actual),
+                })
+            }
+        });
+        result.clone().map_err(io::Error::from)
+    }
+}
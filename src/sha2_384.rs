@@ -0,0 +1,34 @@
+//! This module provides an implementation of the SHA-2 384 hash function.
+//!
+//! The SHA-384 hash function uses the same compression function as [`SHA2_512`](crate::SHA2_512) and differs only in the initial hash value and the digest length.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! # use chksum_sha2_512::Result;
+//! use chksum_sha2_512::sha2_384;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let file = File::open(path)?;
+//! let digest = sha2_384::chksum(file)?;
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "12ecdfd463a85a301b7c29a43bf4b19cdfc6e5e86a5f40396aa6ae3368a7e5b0ed31f3bef2eb3071577ba610b4ed1cb8"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+crate::variant::variant! {
+    hash: SHA2_384,
+    module: sha2_384,
+    name: "SHA-2 384",
+    iv: crate::engine::SHA2_384_IV,
+    length: 48,
+    example: "12ecdfd463a85a301b7c29a43bf4b19cdfc6e5e86a5f40396aa6ae3368a7e5b0ed31f3bef2eb3071577ba610b4ed1cb8",
+    empty: "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b",
+    empty_uppercase: "38B060A751AC96384CD9327EB1B1E36A21FDB71114BE07434C0CC7BF63F6E1DA274EDEBFE76F65FBD51AD2F14898B95B",
+}
@@ -0,0 +1,34 @@
+//! This module provides an implementation of the SHA-2 512/224 hash function.
+//!
+//! The SHA-512/224 hash function uses the same compression function as [`SHA2_512`](crate::SHA2_512) and differs only in the initial hash value and the digest length.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! # use chksum_sha2_512::Result;
+//! use chksum_sha2_512::sha2_512_224;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let file = File::open(path)?;
+//! let digest = sha2_512_224::chksum(file)?;
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "b616fbfea3f10f9fd8ac7c9ce9773cc3ba2a380dbae5822a58e005cf"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+crate::variant::variant! {
+    hash: SHA2_512_224,
+    module: sha2_512_224,
+    name: "SHA-2 512/224",
+    iv: crate::engine::SHA2_512_224_IV,
+    length: 28,
+    example: "b616fbfea3f10f9fd8ac7c9ce9773cc3ba2a380dbae5822a58e005cf",
+    empty: "6ed0dd02806fa89e25de060c19d3ac86cabb87d6a0ddd05c333b84f4",
+    empty_uppercase: "6ED0DD02806FA89E25DE060C19D3AC86CABB87D6A0DDD05C333B84F4",
+}
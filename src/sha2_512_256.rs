@@ -0,0 +1,34 @@
+//! This module provides an implementation of the SHA-2 512/256 hash function.
+//!
+//! The SHA-512/256 hash function uses the same compression function as [`SHA2_512`](crate::SHA2_512) and differs only in the initial hash value and the digest length.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! # use chksum_sha2_512::Result;
+//! use chksum_sha2_512::sha2_512_256;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let file = File::open(path)?;
+//! let digest = sha2_512_256::chksum(file)?;
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "b7813173e5837a02dc6ee8016a4b42a9010aa1faeaa1305be0531f0df7516217"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+crate::variant::variant! {
+    hash: SHA2_512_256,
+    module: sha2_512_256,
+    name: "SHA-2 512/256",
+    iv: crate::engine::SHA2_512_256_IV,
+    length: 32,
+    example: "b7813173e5837a02dc6ee8016a4b42a9010aa1faeaa1305be0531f0df7516217",
+    empty: "c672b8d1ef56ed28ab87c3622c5114069bdd3ad7b8f9737498d0c01ecef0967a",
+    empty_uppercase: "C672B8D1EF56ED28AB87C3622C5114069BDD3AD7B8F9737498D0C01ECEF0967A",
+}
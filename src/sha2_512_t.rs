@@ -0,0 +1,26 @@
+//! This module provides the initial hash value generation for the SHA-512/t family as described in section 5.3.6 of [FIPS PUB 180-4](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf).
+//!
+//! The [`SHA2_512_224`](crate::SHA2_512_224) and [`SHA2_512_256`](crate::SHA2_512_256) variants use precomputed values that are equal to the ones generated by [`initial_hash_value`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha2_512 as sha2_512;
+//!
+//! let iv = sha2_512::sha2_512_t::initial_hash_value(256).unwrap();
+//! assert_eq!(iv[0], 0x22312194FC2BF72C);
+//! ```
+
+use crate::engine::{self, STATE_LENGTH_QWORDS};
+
+/// Generates the initial hash value for SHA-512/t.
+///
+/// Returns [`None`] if `t` is not a valid output length, i.e. if it is `0`, `384` or not less than `512`.
+#[must_use]
+pub fn initial_hash_value(t: usize) -> Option<[u64; STATE_LENGTH_QWORDS]> {
+    if t == 0 || t == 384 || t >= 512 {
+        None
+    } else {
+        Some(engine::sha2_512_t_iv(t))
+    }
+}
@@ -0,0 +1,384 @@
+//! The definition shared by the truncated hash variants.
+//!
+//! Every variant from the SHA-512 family exposes the same interface and differs only in the initial hash value and the digest length, so the modules are generated by the [`variant`] macro.
+
+/// Defines the functions, the hash and the digest types of a SHA-512 family variant in the invoking module.
+macro_rules! variant {
+    (
+        hash: $hash:ident,
+        module: $module:ident,
+        name: $name:literal,
+        iv: $iv:path,
+        length: $length:literal,
+        example: $example:literal,
+        empty: $empty:literal,
+        empty_uppercase: $empty_uppercase:literal $(,)?
+    ) => {
+        use std::fmt::{self, Display, Formatter, LowerHex, UpperHex};
+        use std::hash::{Hash as StdHash, Hasher};
+        use std::result;
+        use std::str::FromStr;
+
+        use crate::engine::Engine;
+        use crate::{core, ct_eq, decode_hex, fmt_hex, FormatError, Result};
+
+        /// Digest length in bytes.
+        pub const DIGEST_LENGTH_BYTES: usize = $length;
+
+        #[cfg(feature = "reader")]
+        #[doc = concat!("A specialized [`Reader`](chksum_reader::Reader) type with the [`", stringify!($hash), "`] hash algorithm.")]
+        pub type Reader<R> = chksum_reader::Reader<R, $hash>;
+
+        #[cfg(all(feature = "reader", feature = "async-runtime-tokio"))]
+        #[doc = concat!("A specialized [`AsyncReader`](chksum_reader::AsyncReader) type with the [`", stringify!($hash), "`] hash algorithm.")]
+        pub type AsyncReader<R> = chksum_reader::AsyncReader<R, $hash>;
+
+        #[cfg(feature = "writer")]
+        #[doc = concat!("A specialized [`Writer`](chksum_writer::Writer) type with the [`", stringify!($hash), "`] hash algorithm.")]
+        pub type Writer<W> = chksum_writer::Writer<W, $hash>;
+
+        #[cfg(all(feature = "writer", feature = "async-runtime-tokio"))]
+        #[doc = concat!("A specialized [`AsyncWriter`](chksum_writer::AsyncWriter) type with the [`", stringify!($hash), "`] hash algorithm.")]
+        pub type AsyncWriter<W> = chksum_writer::AsyncWriter<W, $hash>;
+
+        /// Creates a new hash.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        #[doc = concat!("use chksum_sha2_512::", stringify!($module), ";")]
+        ///
+        #[doc = concat!("let mut hash = ", stringify!($module), "::new();")]
+        /// hash.update(b"example data");
+        /// let digest = hash.digest();
+        /// assert_eq!(
+        ///     digest.to_hex_lowercase(),
+        #[doc = concat!("    \"", $example, "\"")]
+        /// );
+        /// ```
+        #[must_use]
+        pub fn new() -> $hash {
+            $hash::new()
+        }
+
+        /// Creates a default hash.
+        #[must_use]
+        pub fn default() -> $hash {
+            core::default()
+        }
+
+        /// Computes the hash of the given input.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        #[doc = concat!("use chksum_sha2_512::", stringify!($module), ";")]
+        ///
+        /// let data = b"example data";
+        #[doc = concat!("let digest = ", stringify!($module), "::hash(data);")]
+        /// assert_eq!(
+        ///     digest.to_hex_lowercase(),
+        #[doc = concat!("    \"", $example, "\"")]
+        /// );
+        /// ```
+        pub fn hash(data: impl core::Hashable) -> Digest {
+            core::hash::<$hash>(data)
+        }
+
+        /// Computes the hash of the given input.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        #[doc = concat!("use chksum_sha2_512::", stringify!($module), ";")]
+        ///
+        /// let data = b"example data";
+        #[doc = concat!("if let Ok(digest) = ", stringify!($module), "::chksum(data) {")]
+        ///     assert_eq!(
+        ///         digest.to_hex_lowercase(),
+        #[doc = concat!("        \"", $example, "\"")]
+        ///     );
+        /// }
+        /// ```
+        pub fn chksum(data: impl core::Chksumable) -> Result<Digest> {
+            core::chksum::<$hash>(data)
+        }
+
+        /// Computes the hash of the given input.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        #[doc = concat!("use chksum_sha2_512::", stringify!($module), ";")]
+        ///
+        /// # async fn wrapper() {
+        /// let data = b"example data";
+        #[doc = concat!("if let Ok(digest) = ", stringify!($module), "::async_chksum(data).await {")]
+        ///     assert_eq!(
+        ///         digest.to_hex_lowercase(),
+        #[doc = concat!("        \"", $example, "\"")]
+        ///     );
+        /// }
+        /// # }
+        /// ```
+        #[cfg(feature = "async-runtime-tokio")]
+        pub async fn async_chksum(data: impl core::AsyncChksumable) -> Result<Digest> {
+            core::async_chksum::<$hash>(data).await
+        }
+
+        #[doc = concat!("The ", $name, " hash instance.")]
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct $hash {
+            inner: Engine,
+        }
+
+        impl $hash {
+            /// Calculates the hash digest of an input data.
+            #[must_use]
+            pub fn hash<T>(data: T) -> Digest
+            where
+                T: AsRef<[u8]>,
+            {
+                let mut hash = Self::new();
+                hash.update(data);
+                hash.digest()
+            }
+
+            /// Creates a new hash.
+            #[must_use]
+            pub fn new() -> Self {
+                let inner = Engine::new($iv);
+                Self { inner }
+            }
+
+            /// Updates the hash state with an input data.
+            pub fn update<T>(&mut self, data: T)
+            where
+                T: AsRef<[u8]>,
+            {
+                self.inner.update(data.as_ref());
+            }
+
+            /// Resets the hash state to its initial state.
+            pub fn reset(&mut self) {
+                self.inner.reset();
+            }
+
+            /// Produces the hash digest.
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!("use chksum_sha2_512::", stringify!($hash), ";")]
+            ///
+            #[doc = concat!("let hash = ", stringify!($hash), "::new();")]
+            /// let digest = hash.digest();
+            /// assert_eq!(
+            ///     digest.to_hex_lowercase(),
+            #[doc = concat!("    \"", $empty, "\"")]
+            /// );
+            /// ```
+            #[must_use]
+            pub fn digest(&self) -> Digest {
+                Digest::new(self.inner.digest())
+            }
+        }
+
+        impl Default for $hash {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl core::Hash for $hash {
+            type Digest = Digest;
+
+            fn update<T>(&mut self, data: T)
+            where
+                T: AsRef<[u8]>,
+            {
+                self.update(data);
+            }
+
+            fn reset(&mut self) {
+                self.reset();
+            }
+
+            fn digest(&self) -> Self::Digest {
+                self.digest()
+            }
+        }
+
+        /// A hash digest.
+        ///
+        /// Equality comparisons are performed in constant time, see [`Digest::ct_eq`].
+        #[derive(Clone, Debug)]
+        pub struct Digest([u8; DIGEST_LENGTH_BYTES]);
+
+        impl Digest {
+            /// Creates a new digest.
+            #[must_use]
+            pub const fn new(digest: [u8; DIGEST_LENGTH_BYTES]) -> Self {
+                Self(digest)
+            }
+
+            /// Returns a byte slice of the digest's contents.
+            #[must_use]
+            pub const fn as_bytes(&self) -> &[u8] {
+                let Self(inner) = self;
+                inner
+            }
+
+            /// Consumes the digest, returning the digest bytes.
+            #[must_use]
+            pub fn into_inner(self) -> [u8; DIGEST_LENGTH_BYTES] {
+                let Self(inner) = self;
+                inner
+            }
+
+            /// Returns a string in the lowercase hexadecimal representation.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            #[doc = concat!("use chksum_sha2_512::", stringify!($module), ";")]
+            ///
+            #[doc = concat!("let digest = ", stringify!($module), "::new().digest();")]
+            /// assert_eq!(
+            ///     digest.to_hex_lowercase(),
+            #[doc = concat!("    \"", $empty, "\"")]
+            /// );
+            /// ```
+            #[must_use]
+            pub fn to_hex_lowercase(&self) -> String {
+                format!("{self:x}")
+            }
+
+            /// Returns a string in the uppercase hexadecimal representation.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            #[doc = concat!("use chksum_sha2_512::", stringify!($module), ";")]
+            ///
+            #[doc = concat!("let digest = ", stringify!($module), "::new().digest();")]
+            /// assert_eq!(
+            ///     digest.to_hex_uppercase(),
+            #[doc = concat!("    \"", $empty_uppercase, "\"")]
+            /// );
+            /// ```
+            #[must_use]
+            pub fn to_hex_uppercase(&self) -> String {
+                format!("{self:X}")
+            }
+
+            /// Parses a digest from its hexadecimal representation.
+            ///
+            /// Both lowercase and uppercase digits are accepted.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`FormatError::InvalidLength`] if the input length is invalid and [`FormatError::InvalidCharacter`] with the byte offset of the first character that is not a hexadecimal digit.
+            pub fn from_hex(digest: &str) -> result::Result<Self, FormatError> {
+                decode_hex(digest).map(Self::new)
+            }
+
+            /// Compares two digests in constant time.
+            #[must_use]
+            pub fn ct_eq(&self, other: &Self) -> bool {
+                ct_eq(self.as_bytes(), other.as_bytes())
+            }
+        }
+
+        impl core::Digest for Digest {}
+
+        impl PartialEq for Digest {
+            fn eq(&self, other: &Self) -> bool {
+                self.ct_eq(other)
+            }
+        }
+
+        impl Eq for Digest {}
+
+        impl PartialEq<[u8]> for Digest {
+            fn eq(&self, other: &[u8]) -> bool {
+                ct_eq(self.as_bytes(), other)
+            }
+        }
+
+        impl PartialEq<str> for Digest {
+            fn eq(&self, other: &str) -> bool {
+                Self::from_hex(other).is_ok_and(|other| self.ct_eq(&other))
+            }
+        }
+
+        impl PartialEq<&str> for Digest {
+            fn eq(&self, other: &&str) -> bool {
+                self == *other
+            }
+        }
+
+        impl StdHash for Digest {
+            fn hash<H>(&self, state: &mut H)
+            where
+                H: Hasher,
+            {
+                self.as_bytes().hash(state);
+            }
+        }
+
+        impl AsRef<[u8]> for Digest {
+            fn as_ref(&self) -> &[u8] {
+                self.as_bytes()
+            }
+        }
+
+        impl Display for Digest {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                LowerHex::fmt(self, f)
+            }
+        }
+
+        impl LowerHex for Digest {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                fmt_hex(self.as_bytes(), f, false)
+            }
+        }
+
+        impl UpperHex for Digest {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                fmt_hex(self.as_bytes(), f, true)
+            }
+        }
+
+        impl From<[u8; DIGEST_LENGTH_BYTES]> for Digest {
+            fn from(digest: [u8; DIGEST_LENGTH_BYTES]) -> Self {
+                Self::new(digest)
+            }
+        }
+
+        impl From<Digest> for [u8; DIGEST_LENGTH_BYTES] {
+            fn from(digest: Digest) -> Self {
+                digest.into_inner()
+            }
+        }
+
+        impl FromStr for Digest {
+            type Err = FormatError;
+
+            fn from_str(digest: &str) -> result::Result<Self, Self::Err> {
+                Self::from_hex(digest)
+            }
+        }
+
+        impl TryFrom<&str> for Digest {
+            type Error = FormatError;
+
+            fn try_from(digest: &str) -> result::Result<Self, Self::Error> {
+                Self::from_hex(digest)
+            }
+        }
+    };
+}
+
+pub(crate) use variant;
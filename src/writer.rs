@@ -42,13 +42,26 @@
 //! # }
 //! ```
 
-use std::io::Write;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use chksum_writer as writer;
 #[cfg(feature = "async-runtime-tokio")]
 use tokio::io::AsyncWrite;
 
-use crate::SHA2_512;
+use crate::manifest::{Entry, Manifest, Mode};
+use crate::progress::{ProgressHash, ProgressReporter};
+use crate::{Digest, SHA2_512};
+
+/// The extension of the sidecar file written by [`AtomicFileWriter::commit`].
+pub const SIDECAR_EXTENSION: &str = "sha512";
+
+/// The number of attempts to create a unique temporary file.
+const TEMP_ATTEMPTS: u32 = 128;
 
 /// A specialized [`Writer`](writer::Writer) type with the [`SHA2_512`] hash algorithm.
 pub type Writer<W> = writer::Writer<W, SHA2_512>;
@@ -57,6 +70,13 @@ pub type Writer<W> = writer::Writer<W, SHA2_512>;
 /// A specialized [`AsyncWriter`](writer::AsyncWriter) type with the [`SHA2_512`] hash algorithm.
 pub type AsyncWriter<R> = writer::AsyncWriter<R, SHA2_512>;
 
+/// A specialized [`Writer`](writer::Writer) type with the [`ProgressHash`] hash reporting the progress.
+pub type ProgressWriter<W> = writer::Writer<W, ProgressHash>;
+
+#[cfg(feature = "async-runtime-tokio")]
+/// A specialized [`AsyncWriter`](writer::AsyncWriter) type with the [`ProgressHash`] hash reporting the progress.
+pub type AsyncProgressWriter<W> = writer::AsyncWriter<W, ProgressHash>;
+
 /// Creates new [`Writer`].
 pub fn new(inner: impl Write) -> Writer<impl Write> {
     writer::new(inner)
@@ -67,6 +87,11 @@ pub fn with_hash(inner: impl Write, hash: SHA2_512) -> Writer<impl Write> {
     writer::with_hash(inner, hash)
 }
 
+/// Creates new [`ProgressWriter`] reporting the progress to the provided reporter.
+pub fn with_progress(inner: impl Write, reporter: ProgressReporter) -> ProgressWriter<impl Write> {
+    writer::with_hash(inner, ProgressHash::new(reporter))
+}
+
 #[cfg(feature = "async-runtime-tokio")]
 /// Creates new [`AsyncWriter`].
 pub fn async_new(inner: impl AsyncWrite) -> AsyncWriter<impl AsyncWrite> {
@@ -78,3 +103,191 @@ pub fn async_new(inner: impl AsyncWrite) -> AsyncWriter<impl AsyncWrite> {
 pub fn async_with_hash(inner: impl AsyncWrite, hash: SHA2_512) -> AsyncWriter<impl AsyncWrite> {
     writer::async_with_hash(inner, hash)
 }
+
+#[cfg(feature = "async-runtime-tokio")]
+/// Creates new [`AsyncProgressWriter`] reporting the progress to the provided reporter.
+pub fn async_with_progress(inner: impl AsyncWrite, reporter: ProgressReporter) -> AsyncProgressWriter<impl AsyncWrite> {
+    writer::async_with_hash(inner, ProgressHash::new(reporter))
+}
+
+/// A writer persisting a file atomically together with its digest sidecar.
+///
+/// The data is written to a temporary file in the target directory. On [`AtomicFileWriter::commit`] the file is synchronized to disk and renamed into place, then a `<name>.sha512` sidecar is written in the GNU coreutils format, so the file can be checked with `sha512sum -c`. The sidecar is replaced atomically as well.
+///
+/// If the writer is dropped without committing, the temporary file is removed and the target is left untouched.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::io::Write;
+///
+/// use chksum_sha2_512 as sha2_512;
+/// use sha2_512::writer::AtomicFileWriter;
+///
+/// # fn wrapper(dir: &Path) -> std::io::Result<()> {
+/// let mut writer = AtomicFileWriter::new(dir.join("file"))?;
+/// writer.write_all(b"example data")?;
+/// let digest = writer.commit()?;
+/// assert_eq!(digest, sha2_512::hash(b"example data"));
+/// // `file` and `file.sha512` now exist in the directory
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AtomicFileWriter {
+    writer: Option<Writer<File>>,
+    path: PathBuf,
+    temp_path: PathBuf,
+    /// Whether the temporary file was renamed into place.
+    committed: bool,
+}
+
+impl AtomicFileWriter {
+    /// Creates a temporary file in the directory of the target path.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the path has no file name or the temporary file cannot be created.
+    pub fn new(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let (file, temp_path) = create_temp(&path)?;
+        let writer = Some(Writer::new(file));
+        Ok(Self {
+            writer,
+            path,
+            temp_path,
+            committed: false,
+        })
+    }
+
+    /// Returns the target path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path of the sidecar file.
+    pub fn sidecar_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".");
+        path.push(SIDECAR_EXTENSION);
+        PathBuf::from(path)
+    }
+
+    /// Returns the digest of the data written so far.
+    pub fn digest(&self) -> Digest {
+        self.writer().digest()
+    }
+
+    /// Synchronizes the data to disk, renames the file into place and writes the sidecar, returning the digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if any of the steps fails. If the file was not renamed yet, the temporary file is removed.
+    pub fn commit(mut self) -> io::Result<Digest> {
+        let mut writer = self.writer.take().expect("writer is present until committed");
+        writer.flush()?;
+        let digest = writer.digest();
+        let file = writer.into_inner();
+        file.sync_all()?;
+        drop(file);
+        persist(&self.temp_path, &self.path)?;
+        self.committed = true;
+
+        let name = self.path.file_name().expect("path has a file name");
+        let manifest: Manifest = [Entry::new(digest.clone(), name, Mode::Text)].into_iter().collect();
+        let sidecar_path = self.sidecar_path();
+        let (mut file, temp_path) = create_temp(&sidecar_path)?;
+        let result = file
+            .write_all(&manifest.to_bytes())
+            .and_then(|()| file.sync_all())
+            .and_then(|()| persist(&temp_path, &sidecar_path));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        result?;
+        sync_dir(&self.path)?;
+        Ok(digest)
+    }
+
+    fn writer(&self) -> &Writer<File> {
+        self.writer.as_ref().expect("writer is present until committed")
+    }
+
+    fn writer_mut(&mut self) -> &mut Writer<File> {
+        self.writer.as_mut().expect("writer is present until committed")
+    }
+}
+
+impl Write for AtomicFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer_mut().flush()
+    }
+}
+
+impl Drop for AtomicFileWriter {
+    fn drop(&mut self) {
+        // Once renamed into place, the temporary file no longer exists and the path may belong to another file.
+        if !self.committed {
+            let _ = fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+/// Returns the directory of the path, using the current directory for bare file names.
+fn parent(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+/// Creates a new hidden temporary file next to the path.
+fn create_temp(path: &Path) -> io::Result<(File, PathBuf)> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let dir = parent(path);
+    for _ in 0..TEMP_ATTEMPTS {
+        let mut temp_name = OsString::from(".");
+        temp_name.push(name);
+        let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+        temp_name.push(format!(".{}.{counter}.tmp", process::id()));
+        let temp_path = dir.join(temp_name);
+        match OpenOptions::new().write(true).create_new(true).open(&temp_path) {
+            Ok(file) => return Ok((file, temp_path)),
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(error),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "cannot create a unique temporary file",
+    ))
+}
+
+/// Renames the temporary file into place, removing it on failure.
+fn persist(temp_path: &Path, path: &Path) -> io::Result<()> {
+    fs::rename(temp_path, path).map_err(|error| {
+        let _ = fs::remove_file(temp_path);
+        error
+    })
+}
+
+/// Synchronizes the directory of the path, so the renames are durable.
+#[cfg(unix)]
+fn sync_dir(path: &Path) -> io::Result<()> {
+    File::open(parent(path))?.sync_all()
+}
+
+/// Synchronizes the directory of the path, so the renames are durable.
+#[cfg(not(unix))]
+fn sync_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
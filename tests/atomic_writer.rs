@@ -0,0 +1,100 @@
+#[cfg(feature = "writer")]
+use std::fs;
+#[cfg(feature = "writer")]
+use std::io::Write;
+
+#[cfg(feature = "writer")]
+use assert_fs::TempDir;
+#[cfg(feature = "writer")]
+use chksum_sha2_512::hash;
+#[cfg(feature = "writer")]
+use chksum_sha2_512::manifest::Manifest;
+#[cfg(feature = "writer")]
+use chksum_sha2_512::writer::AtomicFileWriter;
+
+#[cfg_attr(not(feature = "writer"), ignore)]
+#[test]
+fn commit() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "writer")]
+    {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.join("file");
+
+        let mut writer = AtomicFileWriter::new(&path)?;
+        writer.write_all(b"example ")?;
+        writer.write_all(b"data")?;
+        assert!(!path.exists());
+        assert_eq!(writer.digest(), hash(b"example data"));
+        assert_eq!(writer.sidecar_path(), temp_dir.join("file.sha512"));
+        let digest = writer.commit()?;
+        assert_eq!(digest, hash(b"example data"));
+
+        assert_eq!(fs::read(&path)?, b"example data");
+        let sidecar = fs::read_to_string(temp_dir.join("file.sha512"))?;
+        assert_eq!(sidecar, format!("{}  file\n", digest.to_hex_lowercase()));
+        let manifest = Manifest::parse(&sidecar)?;
+        assert_eq!(manifest.entries()[0].digest(), &digest);
+        assert_eq!(fs::read_dir(&temp_dir)?.count(), 2);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "writer"), ignore)]
+#[test]
+fn commit_replaces_existing() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "writer")]
+    {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.join("file");
+        fs::write(&path, b"old data")?;
+        fs::write(temp_dir.join("file.sha512"), b"stale")?;
+
+        let mut writer = AtomicFileWriter::new(&path)?;
+        writer.write_all(b"example data")?;
+        assert_eq!(fs::read(&path)?, b"old data");
+        let digest = writer.commit()?;
+
+        assert_eq!(fs::read(&path)?, b"example data");
+        let sidecar = fs::read_to_string(temp_dir.join("file.sha512"))?;
+        assert!(sidecar.starts_with(&digest.to_hex_lowercase()));
+        assert_eq!(fs::read_dir(&temp_dir)?.count(), 2);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "writer"), ignore)]
+#[test]
+fn drop_discards() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "writer")]
+    {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.join("file");
+        fs::write(&path, b"old data")?;
+
+        let mut writer = AtomicFileWriter::new(&path)?;
+        writer.write_all(b"example data")?;
+        assert_eq!(fs::read_dir(&temp_dir)?.count(), 2);
+        drop(writer);
+
+        assert_eq!(fs::read(&path)?, b"old data");
+        assert!(!temp_dir.join("file.sha512").exists());
+        assert_eq!(fs::read_dir(&temp_dir)?.count(), 1);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "writer"), ignore)]
+#[test]
+fn invalid_path() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "writer")]
+    {
+        let temp_dir = TempDir::new()?;
+        assert!(AtomicFileWriter::new(temp_dir.join("..")).is_err());
+        assert!(AtomicFileWriter::new(temp_dir.join("missing").join("file")).is_err());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use std::fs::File;
+use std::io::{self, Error as IoError};
+use std::num::NonZeroUsize;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_512::block::{self, BlockError};
+use chksum_sha2_512::{chksum, hash, BlockDigests, BlockHasher, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Block(#[from] BlockError),
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+fn data() -> Vec<u8> {
+    (0..100_000).map(|index| (index % 251) as u8).collect()
+}
+
+fn block_length(length: usize) -> NonZeroUsize {
+    NonZeroUsize::new(length).unwrap()
+}
+
+#[test]
+fn blocks() -> Result<(), Error> {
+    let data = data();
+    for length in [1, 127, 128, 4096, 99_999, 100_000, 1_000_000] {
+        let digests = block::chksum(data.as_slice(), block_length(length))?;
+        let expected: Vec<_> = data.chunks(length).map(hash).collect();
+        assert_eq!(digests.blocks(), expected);
+        assert_eq!(digests.digest(), &hash(&data));
+        assert_eq!(digests.block_length(), length as u64);
+        assert_eq!(digests.length(), 100_000);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn empty() -> Result<(), Error> {
+    let digests = block::chksum(io::empty(), block_length(4))?;
+    assert!(digests.blocks().is_empty());
+    assert_eq!(digests.digest(), &hash(b""));
+
+    Ok(())
+}
+
+#[test]
+fn file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file");
+    file.write_binary(&data())?;
+
+    let digests = block::chksum(File::open(file.path())?, block_length(4096))?;
+    assert_eq!(digests.digest(), &chksum(file.path())?);
+    assert_eq!(digests.blocks().len(), 25);
+
+    Ok(())
+}
+
+#[test]
+fn hasher_updates() -> Result<(), Error> {
+    let data = data();
+    let mut hasher = BlockHasher::new(block_length(1000));
+    for chunk in data.chunks(333) {
+        hasher.update(chunk);
+    }
+    assert_eq!(hasher.finalize(), block::chksum(data.as_slice(), block_length(1000))?);
+
+    let mut hasher = BlockHasher::new(block_length(1000));
+    io::copy(&mut data.as_slice(), &mut hasher)?;
+    assert_eq!(hasher.finalize(), block::chksum(data.as_slice(), block_length(1000))?);
+
+    Ok(())
+}
+
+#[test]
+fn serialization() -> Result<(), Error> {
+    let data = data();
+    for length in [1000, 100_000] {
+        let digests = block::chksum(data.as_slice(), block_length(length))?;
+        let bytes = digests.to_bytes();
+        assert_eq!(BlockDigests::from_bytes(&bytes)?, digests);
+    }
+
+    let digests = block::chksum(io::empty(), block_length(4))?;
+    assert_eq!(BlockDigests::from_bytes(&digests.to_bytes())?, digests);
+
+    Ok(())
+}
+
+#[test]
+fn serialization_errors() -> Result<(), Error> {
+    let digests = block::chksum(&b"example data"[..], block_length(4))?;
+    let bytes = digests.to_bytes();
+
+    assert_eq!(
+        BlockDigests::from_bytes(&bytes[..10]),
+        Err(BlockError::Truncated { value: 10, minimum: 89 })
+    );
+
+    let mut invalid = bytes.clone();
+    invalid[0] = b'X';
+    assert_eq!(BlockDigests::from_bytes(&invalid), Err(BlockError::InvalidMagic));
+
+    let mut invalid = bytes.clone();
+    invalid[8] = 2;
+    assert_eq!(
+        BlockDigests::from_bytes(&invalid),
+        Err(BlockError::UnsupportedVersion { version: 2 })
+    );
+
+    let mut invalid = bytes.clone();
+    invalid[9..17].fill(0);
+    assert_eq!(BlockDigests::from_bytes(&invalid), Err(BlockError::InvalidBlockLength));
+
+    assert_eq!(
+        BlockDigests::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(BlockError::InvalidLength {
+            value: 191,
+            proper: 192
+        })
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn reader() -> Result<(), Error> {
+    #[cfg(feature = "reader")]
+    {
+        use chksum_sha2_512::reader;
+
+        let data = data();
+        let mut reader = reader::new(data.as_slice());
+        let digests = block::chksum(&mut reader, block_length(4096))?;
+        assert_eq!(reader.digest(), *digests.digest());
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_same_as_sync() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file");
+        file.write_binary(&data())?;
+
+        let digests = block::async_chksum(tokio::fs::File::open(file.path()).await?, block_length(4096)).await?;
+        assert_eq!(digests, block::chksum(File::open(file.path())?, block_length(4096))?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::PathChild;
+use assert_fs::TempDir;
+use chksum_sha2_512::cancel::{self, CancelError, CancellationToken};
+use chksum_sha2_512::progress::{Progress, ProgressReporter};
+use chksum_sha2_512::{
+    chksum,
+    chksum_dir,
+    chksum_dir_with_context,
+    DirContext,
+    DirError,
+    DirOptions,
+    Error as ChksumError,
+};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Cancel(#[from] CancelError),
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Dir(#[from] DirError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+}
+
+fn tree() -> Result<TempDir, Error> {
+    let temp_dir = common::tree_with(&[
+        ("a.txt", b"example data"),
+        ("sub/b.txt", &[0; 100_000]),
+        ("sub/c.txt", b""),
+        ("sub.txt", b"sorted after sub"),
+    ])?;
+    Ok(temp_dir)
+}
+
+fn context(token: &CancellationToken) -> DirContext {
+    DirContext::new().with_cancellation(token.clone())
+}
+
+#[test]
+fn token() {
+    let token = CancellationToken::new();
+    let clone = token.clone();
+    assert!(!token.is_cancelled());
+    clone.cancel();
+    assert!(token.is_cancelled());
+    assert!(clone.is_cancelled());
+}
+
+#[test]
+fn same_as_chksum() -> Result<(), Error> {
+    let temp_dir = tree()?;
+    let token = CancellationToken::new();
+    assert_eq!(cancel::chksum(temp_dir.path(), &token)?, chksum(temp_dir.path())?);
+    assert_eq!(
+        cancel::chksum(temp_dir.child("sub/b.txt").path(), &token)?,
+        chksum(temp_dir.child("sub/b.txt").path())?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cancelled() -> Result<(), Error> {
+    let temp_dir = tree()?;
+    let token = CancellationToken::new();
+    token.cancel();
+    let result = cancel::chksum(temp_dir.path(), &token);
+    assert!(matches!(result, Err(CancelError::Cancelled)));
+
+    Ok(())
+}
+
+#[test]
+fn missing() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let result = cancel::chksum(temp_dir.child("missing").path(), &CancellationToken::new());
+    assert!(matches!(result, Err(CancelError::Io(_))));
+
+    Ok(())
+}
+
+#[test]
+fn dir() -> Result<(), Error> {
+    let temp_dir = tree()?;
+    let options = DirOptions::new();
+    let token = CancellationToken::new();
+    assert_eq!(
+        chksum_dir_with_context(temp_dir.path(), &options, context(&token))?,
+        chksum_dir(temp_dir.path(), &options)?
+    );
+
+    token.cancel();
+    let result = chksum_dir_with_context(temp_dir.path(), &options, context(&token));
+    assert!(matches!(result, Err(DirError::Cancelled)));
+
+    Ok(())
+}
+
+#[test]
+fn dir_with_progress() -> Result<(), Error> {
+    let temp_dir = tree()?;
+    let token = CancellationToken::new();
+    let reporter = {
+        let token = token.clone();
+        ProgressReporter::new(move |progress: &Progress| {
+            if progress.processed() > 0 {
+                token.cancel();
+            }
+        })
+        .with_interval(1)
+    };
+    let context = context(&token).with_progress(reporter);
+    let result = chksum_dir_with_context(temp_dir.path(), &DirOptions::new(), context);
+    assert!(matches!(result, Err(DirError::Cancelled)));
+    assert!(token.is_cancelled());
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_same_as_sync() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use chksum_sha2_512::{async_chksum, async_chksum_dir_with_context};
+
+        let temp_dir = tree()?;
+        let options = DirOptions::new();
+        let token = CancellationToken::new();
+        assert_eq!(
+            cancel::async_chksum(temp_dir.path(), &token).await?,
+            async_chksum(temp_dir.path()).await?
+        );
+        assert_eq!(
+            async_chksum_dir_with_context(temp_dir.path(), &options, context(&token)).await?,
+            chksum_dir(temp_dir.path(), &options)?
+        );
+
+        token.cancel();
+        let result = cancel::async_chksum(temp_dir.path(), &token).await;
+        assert!(matches!(result, Err(CancelError::Cancelled)));
+        let result = async_chksum_dir_with_context(temp_dir.path(), &options, context(&token)).await;
+        assert!(matches!(result, Err(DirError::Cancelled)));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,285 @@
+mod common;
+
+use std::io::Error as IoError;
+#[cfg(feature = "cli")]
+use std::io::Write;
+#[cfg(feature = "cli")]
+use std::process::{Command, Output, Stdio};
+
+use assert_fs::fixture::FixtureError;
+#[cfg(feature = "cli")]
+use assert_fs::prelude::{FileWriteBin, PathChild};
+#[cfg(feature = "cli")]
+use assert_fs::TempDir;
+use chksum_sha2_512::Error as ChksumError;
+
+#[cfg(feature = "cli")]
+const EMPTY: &str = "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e";
+#[cfg(feature = "cli")]
+const EXAMPLE: &str = "ed59c5759a9ece516cec0c0623142d0e9fe70a27d750eee7fd38f4550d50addd873d0fa1a51fc823c1e3d5cada203f4a05d8325caacb7d3e0727a701f3f07e5f";
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[cfg(feature = "cli")]
+fn run(dir: &TempDir, args: &[&str], stdin: &[u8]) -> Result<Output, Error> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_chksum-sha2-512"))
+        .args(args)
+        .current_dir(dir.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut input) = child.stdin.take() {
+        input.write_all(stdin)?;
+    }
+    Ok(child.wait_with_output()?)
+}
+
+#[cfg(feature = "cli")]
+fn tree() -> Result<TempDir, Error> {
+    let temp_dir = common::tree_with(&[
+        ("empty", b""),
+        ("data", b"example data"),
+        ("a\\b", b"example data"),
+        ("dir/nested", b""),
+    ])?;
+    Ok(temp_dir)
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn generate() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = tree()?;
+
+        let output = run(&temp_dir, &["empty", "data", "a\\b"], b"")?;
+        assert!(output.status.success());
+        let expected = format!("{EMPTY}  empty\n{EXAMPLE}  data\n\\{EXAMPLE}  a\\\\b\n");
+        assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+
+        let output = run(&temp_dir, &["--binary", "--uppercase", "empty"], b"")?;
+        let expected = format!("{} *empty\n", EMPTY.to_uppercase());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+
+        let output = run(&temp_dir, &["--tag", "--zero", "empty", "a\\b"], b"")?;
+        let expected = format!("SHA512 (empty) = {EMPTY}\0SHA512 (a\\b) = {EXAMPLE}\0");
+        assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn generate_stdin() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = TempDir::new()?;
+
+        let output = run(&temp_dir, &[], b"example data")?;
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), format!("{EXAMPLE}  -\n"));
+
+        let output = run(&temp_dir, &["-"], b"")?;
+        assert_eq!(String::from_utf8_lossy(&output.stdout), format!("{EMPTY}  -\n"));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn generate_directories() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = tree()?;
+
+        let output = run(&temp_dir, &["dir"], b"")?;
+        assert!(output.status.success());
+        let expected = format!("{}  dir\n", chksum_sha2_512::chksum(temp_dir.child("dir").path())?);
+        assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+
+        let output = run(&temp_dir, &["--recursive", "."], b"")?;
+        assert!(output.status.success());
+        let expected = format!("\\{EXAMPLE}  ./a\\\\b\n{EXAMPLE}  ./data\n{EMPTY}  ./dir/nested\n{EMPTY}  ./empty\n");
+        assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn generate_recursive_symlinks() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = tree()?;
+        symlink("..", temp_dir.child("dir/loop"))?;
+        symlink("nested", temp_dir.child("dir/link"))?;
+
+        let output = run(&temp_dir, &["--recursive", "dir"], b"")?;
+        assert!(output.status.success());
+        let expected = format!("{EMPTY}  dir/link\n{EMPTY}  dir/nested\n");
+        assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn generate_missing() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = tree()?;
+
+        let output = run(&temp_dir, &["missing", "empty"], b"")?;
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), format!("{EMPTY}  empty\n"));
+        assert!(String::from_utf8_lossy(&output.stderr).starts_with("chksum-sha2-512: missing: "));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn check() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = tree()?;
+        let manifest = format!("{EMPTY}  empty\nSHA512 (data) = {EXAMPLE}\n\\{EXAMPLE}  a\\\\b\n");
+        temp_dir.child("SHA512SUMS").write_binary(manifest.as_bytes())?;
+
+        let output = run(&temp_dir, &["--check", "SHA512SUMS"], b"")?;
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "empty: OK\ndata: OK\na\\b: OK\n"
+        );
+
+        let output = run(&temp_dir, &["-c", "--quiet"], manifest.as_bytes())?;
+        assert_eq!(output.status.code(), Some(0));
+        assert!(output.stdout.is_empty());
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn check_comments_and_crlf() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = tree()?;
+        let manifest = format!("# comment\n{EMPTY}  empty\n\n");
+        temp_dir.child("SHA512SUMS").write_binary(manifest.as_bytes())?;
+
+        let output = run(&temp_dir, &["--strict", "-c", "SHA512SUMS"], b"")?;
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "empty: OK\n");
+        assert!(output.stderr.is_empty());
+
+        let manifest = format!("{EMPTY}  empty\r\nSHA512 (data) = {EXAMPLE}\r\n");
+        let output = run(&temp_dir, &["--strict", "-c"], manifest.as_bytes())?;
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "empty: OK\ndata: OK\n");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn check_failures() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = tree()?;
+        let manifest = format!("{EMPTY}  data\n{EMPTY}  missing\nmalformed\n{EMPTY}  empty\n");
+        temp_dir.child("SHA512SUMS").write_binary(manifest.as_bytes())?;
+
+        let output = run(&temp_dir, &["-c", "SHA512SUMS"], b"")?;
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "data: FAILED\nmissing: FAILED open or read\nempty: OK\n"
+        );
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        #[cfg(unix)]
+        assert!(stderr.contains("chksum-sha2-512: missing: No such file or directory\n"));
+        assert!(stderr.contains("WARNING: 1 line is improperly formatted"));
+        assert!(stderr.contains("WARNING: 1 listed file could not be read"));
+        assert!(stderr.contains("WARNING: 1 computed checksum did NOT match"));
+
+        let output = run(&temp_dir, &["-c", "--status", "SHA512SUMS"], b"")?;
+        assert_eq!(output.status.code(), Some(1));
+        assert!(output.stdout.is_empty());
+        assert!(output.stderr.is_empty());
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn check_options() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = tree()?;
+        let manifest = format!("{EMPTY}  empty\n{EMPTY}  missing\nmalformed\n");
+        temp_dir.child("SHA512SUMS").write_binary(manifest.as_bytes())?;
+
+        let output = run(&temp_dir, &["-c", "--ignore-missing", "SHA512SUMS"], b"")?;
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "empty: OK\n");
+
+        let output = run(&temp_dir, &["-c", "--ignore-missing", "--strict", "SHA512SUMS"], b"")?;
+        assert_eq!(output.status.code(), Some(1));
+
+        let output = run(&temp_dir, &["-c", "--ignore-missing", "--warn", "SHA512SUMS"], b"")?;
+        assert!(String::from_utf8_lossy(&output.stderr)
+            .contains("SHA512SUMS: 3: improperly formatted SHA512 checksum line"));
+
+        let output = run(&temp_dir, &["-c"], b"malformed\n")?;
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("no properly formatted SHA512 checksum lines found"));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn usage() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = TempDir::new()?;
+
+        let output = run(&temp_dir, &["--unknown"], b"")?;
+        assert_eq!(output.status.code(), Some(1));
+
+        let output = run(&temp_dir, &["--check", "--tag"], b"")?;
+        assert_eq!(output.status.code(), Some(1));
+
+        let output = run(&temp_dir, &["--strict"], b"")?;
+        assert_eq!(output.status.code(), Some(1));
+
+        let output = run(&temp_dir, &["--help"], b"")?;
+        assert_eq!(output.status.code(), Some(0));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("Usage: chksum-sha2-512"));
+    }
+
+    Ok(())
+}
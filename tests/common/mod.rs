@@ -0,0 +1,19 @@
+#![allow(dead_code)]
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+
+/// Creates a temporary directory with a file in the root and an empty file in a subdirectory.
+pub fn tree() -> Result<TempDir, FixtureError> {
+    tree_with(&[("a.txt", b"example data"), ("sub/b.txt", b"")])
+}
+
+/// Creates a temporary directory with the given files, creating their parent directories.
+pub fn tree_with(files: &[(&str, &[u8])]) -> Result<TempDir, FixtureError> {
+    let temp_dir = TempDir::new()?;
+    for (path, contents) in files {
+        temp_dir.child(path).write_binary(contents)?;
+    }
+    Ok(temp_dir)
+}
@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use chksum_sha2_512::{hash, Digest, FormatError};
+
+const EMPTY_LOWERCASE: &str = "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e";
+const EMPTY_UPPERCASE: &str = "CF83E1357EEFB8BDF1542850D66D8007D620E4050B5715DC83F4A921D36CE9CE47D0D13C5D85F2B0FF8318D2877EEC2F63B931BD47417A81A538327AF927DA3E";
+
+#[test]
+fn from_hex_lowercase() -> Result<(), FormatError> {
+    let digest = Digest::from_hex(EMPTY_LOWERCASE)?;
+    assert_eq!(digest.to_hex_lowercase(), EMPTY_LOWERCASE);
+
+    Ok(())
+}
+
+#[test]
+fn from_hex_uppercase() -> Result<(), FormatError> {
+    let digest = Digest::from_hex(EMPTY_UPPERCASE)?;
+    assert_eq!(digest.to_hex_lowercase(), EMPTY_LOWERCASE);
+
+    Ok(())
+}
+
+#[test]
+fn from_str_and_try_from() -> Result<(), FormatError> {
+    let digest = Digest::from_str(EMPTY_LOWERCASE)?;
+    assert_eq!(digest.to_hex_uppercase(), EMPTY_UPPERCASE);
+
+    let digest: Digest = EMPTY_UPPERCASE.parse()?;
+    assert_eq!(digest.to_hex_lowercase(), EMPTY_LOWERCASE);
+
+    let digest = Digest::try_from(EMPTY_LOWERCASE)?;
+    assert_eq!(digest.to_hex_lowercase(), EMPTY_LOWERCASE);
+
+    Ok(())
+}
+
+#[test]
+fn from_hex_invalid_length() {
+    let error = Digest::from_hex(&EMPTY_LOWERCASE[1..]).err();
+    let expected = FormatError::InvalidLength {
+        value: 127,
+        proper: 128,
+    };
+    assert_eq!(error, Some(expected));
+
+    let error = Digest::from_hex("").err();
+    let expected = FormatError::InvalidLength { value: 0, proper: 128 };
+    assert_eq!(error, Some(expected));
+}
+
+#[test]
+fn from_hex_invalid_character() {
+    let digest = format!("{}g{}", &EMPTY_LOWERCASE[..10], &EMPTY_LOWERCASE[11..]);
+    let error = Digest::from_hex(&digest).err();
+    let expected = FormatError::InvalidCharacter {
+        character: 'g',
+        offset: 10,
+    };
+    assert_eq!(error, Some(expected));
+
+    let digest = format!("{}ż{}", &EMPTY_LOWERCASE[..64], &EMPTY_LOWERCASE[66..]);
+    let error = Digest::from_hex(&digest).err();
+    let expected = FormatError::InvalidCharacter {
+        character: 'ż',
+        offset: 64,
+    };
+    assert_eq!(error, Some(expected));
+}
+
+#[test]
+fn equality() -> Result<(), FormatError> {
+    let digest = Digest::from_hex(EMPTY_LOWERCASE)?;
+    assert_eq!(digest, hash(b""));
+    assert_ne!(digest, hash(b"data"));
+    assert!(digest.ct_eq(&hash(b"")));
+    assert!(!digest.ct_eq(&hash(b"data")));
+    assert_eq!(digest.clone(), digest);
+
+    Ok(())
+}
+
+#[test]
+fn equality_with_bytes() -> Result<(), FormatError> {
+    let digest = Digest::from_hex(EMPTY_LOWERCASE)?;
+    let bytes = hash(b"").into_inner();
+    assert!(digest == bytes[..]);
+    assert!(digest != bytes[1..]);
+    assert!(digest != [0u8; 64][..]);
+
+    Ok(())
+}
+
+#[test]
+fn equality_with_hex() -> Result<(), FormatError> {
+    let digest = Digest::from_hex(EMPTY_LOWERCASE)?;
+    assert_eq!(digest, EMPTY_LOWERCASE);
+    assert_eq!(digest, EMPTY_UPPERCASE);
+    assert_ne!(digest, &EMPTY_LOWERCASE[1..]);
+    assert_ne!(digest, "not a digest");
+
+    Ok(())
+}
+
+#[test]
+fn hash_set() {
+    let digests: HashSet<Digest> = [hash(b""), hash(b"data"), hash(b"")].into_iter().collect();
+    assert_eq!(digests.len(), 2);
+    assert!(digests.contains(&hash(b"data")));
+}
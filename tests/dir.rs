@@ -0,0 +1,157 @@
+mod common;
+
+use std::fs::{create_dir, rename};
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+#[cfg(feature = "async-runtime-tokio")]
+use chksum_sha2_512::async_chksum_dir;
+use chksum_sha2_512::{chksum_dir, DirError, DirOptions};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Dir(#[from] DirError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[test]
+fn canonical_encoding_is_stable() -> Result<(), Error> {
+    let temp_dir = common::tree()?;
+
+    let digest = chksum_dir(temp_dir.path(), &DirOptions::new())?.to_hex_lowercase();
+    assert_eq!(digest, "1851f4e0e383668982926ecc34633c7ac1a4c331da51d3ebbcaae8eba35a10b04713d3a36c27fc0fb491a25d1b1b882cd86bd319808f9c347a43857a6c5fc8ae");
+
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"example data")?;
+    temp_dir.child("empty").create_dir_all()?;
+    let options = DirOptions::new().include_sizes(true).include_empty_dirs(true);
+    let digest = chksum_dir(temp_dir.path(), &options)?.to_hex_lowercase();
+    assert_eq!(digest, "ab7e3fbb12ec4ccc8971f77f413ad8ada2b4f9846868d158d8d3c0673968ae0a995e2fedc938d6d507a985b789a2bbb4a017cfe446ee8a3bbc426c51e76f01ba");
+
+    Ok(())
+}
+
+#[test]
+fn renaming_changes_digest_only_with_paths() -> Result<(), Error> {
+    let temp_dir = common::tree()?;
+    let with_paths = DirOptions::new();
+    let without_paths = DirOptions::new().include_paths(false);
+
+    let before = chksum_dir(temp_dir.path(), &with_paths)?;
+    let before_without_paths = chksum_dir(temp_dir.path(), &without_paths)?;
+
+    rename(temp_dir.child("a.txt").path(), temp_dir.child("c.txt").path())?;
+    assert_ne!(chksum_dir(temp_dir.path(), &with_paths)?, before);
+    assert_eq!(chksum_dir(temp_dir.path(), &without_paths)?, before_without_paths);
+
+    Ok(())
+}
+
+#[test]
+fn moving_file_changes_digest() -> Result<(), Error> {
+    let temp_dir = common::tree()?;
+    let options = DirOptions::new();
+    let before = chksum_dir(temp_dir.path(), &options)?;
+
+    rename(temp_dir.child("sub/b.txt").path(), temp_dir.child("b.txt").path())?;
+    assert_ne!(chksum_dir(temp_dir.path(), &options)?, before);
+
+    Ok(())
+}
+
+#[test]
+fn empty_directories() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let empty = chksum_dir(temp_dir.path(), &DirOptions::new().include_empty_dirs(true))?;
+
+    create_dir(temp_dir.child("empty").path())?;
+    let without_empty_dirs = DirOptions::new();
+    let with_empty_dirs = DirOptions::new().include_empty_dirs(true);
+    assert_eq!(
+        chksum_dir(temp_dir.path(), &without_empty_dirs)?,
+        chksum_dir(TempDir::new()?.path(), &without_empty_dirs)?
+    );
+    assert_ne!(chksum_dir(temp_dir.path(), &with_empty_dirs)?, empty);
+
+    Ok(())
+}
+
+#[test]
+fn options_change_digest() -> Result<(), Error> {
+    let temp_dir = common::tree()?;
+    let default = chksum_dir(temp_dir.path(), &DirOptions::new())?;
+    let sizes = chksum_dir(temp_dir.path(), &DirOptions::new().include_sizes(true))?;
+    let modes = chksum_dir(temp_dir.path(), &DirOptions::new().include_modes(true))?;
+    assert_ne!(default, sizes);
+    assert_ne!(default, modes);
+    assert_ne!(sizes, modes);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn modes() -> Result<(), Error> {
+    use std::fs::{set_permissions, Permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = common::tree()?;
+    let options = DirOptions::new().include_modes(true);
+    set_permissions(temp_dir.child("a.txt").path(), Permissions::from_mode(0o644))?;
+    let before = chksum_dir(temp_dir.path(), &options)?;
+
+    set_permissions(temp_dir.child("a.txt").path(), Permissions::from_mode(0o600))?;
+    assert_ne!(chksum_dir(temp_dir.path(), &options)?, before);
+    assert_eq!(
+        chksum_dir(temp_dir.path(), &DirOptions::new())?,
+        chksum_dir(temp_dir.path(), &DirOptions::new())?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn file_as_root() -> Result<(), Error> {
+    let temp_dir = common::tree()?;
+    let options = DirOptions::new().include_paths(false);
+    let file = chksum_dir(temp_dir.child("sub/b.txt").path(), &options)?;
+    let dir = chksum_dir(temp_dir.child("sub").path(), &options)?;
+    assert_eq!(file, dir);
+
+    Ok(())
+}
+
+#[test]
+fn missing_path() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let result = chksum_dir(temp_dir.child("missing").path(), &DirOptions::new());
+    assert!(matches!(result, Err(DirError::Io(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_same_as_sync() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = common::tree()?;
+        temp_dir.child("empty").create_dir_all()?;
+        let options = DirOptions::new()
+            .include_sizes(true)
+            .include_modes(true)
+            .include_empty_dirs(true);
+        assert_eq!(
+            async_chksum_dir(temp_dir.path(), &options).await?,
+            chksum_dir(temp_dir.path(), &options)?
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_512::hmac::{self, HmacSha512};
+use chksum_sha2_512::{Error as ChksumError, VerifyError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Verify(#[from] VerifyError),
+}
+
+// Test vectors from RFC 4231, section 4.
+#[test]
+fn rfc4231_test_case_1() {
+    let digest = hmac::hmac([0x0B; 20], "Hi There");
+    assert_eq!(digest.to_hex_lowercase(), "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854");
+}
+
+#[test]
+fn rfc4231_test_case_2() {
+    let digest = hmac::hmac("Jefe", "what do ya want for nothing?");
+    assert_eq!(digest.to_hex_lowercase(), "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737");
+}
+
+#[test]
+fn rfc4231_test_case_3() {
+    let digest = hmac::hmac([0xAA; 20], [0xDD; 50]);
+    assert_eq!(digest.to_hex_lowercase(), "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb");
+}
+
+#[test]
+fn rfc4231_test_case_4() {
+    let key: Vec<u8> = (0x01..=0x19).collect();
+    let digest = hmac::hmac(key, [0xCD; 50]);
+    assert_eq!(digest.to_hex_lowercase(), "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3dba91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd");
+}
+
+#[test]
+fn rfc4231_test_case_5() {
+    let digest = hmac::hmac([0x0C; 20], "Test With Truncation");
+    assert_eq!(&digest.to_hex_lowercase()[..32], "415fad6271580a531d4179bc891d87a6");
+}
+
+#[test]
+fn rfc4231_test_case_6() {
+    let digest = hmac::hmac([0xAA; 131], "Test Using Larger Than Block-Size Key - Hash Key First");
+    assert_eq!(digest.to_hex_lowercase(), "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598");
+}
+
+#[test]
+fn rfc4231_test_case_7() {
+    let digest = hmac::hmac(
+        [0xAA; 131],
+        "This is a test using a larger than block-size key and a larger than block-size data. The key needs to be \
+         hashed before being used by the HMAC algorithm.",
+    );
+    assert_eq!(digest.to_hex_lowercase(), "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58");
+}
+
+#[test]
+fn incremental_update_and_reset() {
+    let mut hmac = HmacSha512::new("Jefe");
+    hmac.update("what do ya ");
+    hmac.update("want for nothing?");
+    assert_eq!(hmac.finalize(), hmac::hmac("Jefe", "what do ya want for nothing?"));
+
+    hmac.reset();
+    assert_eq!(hmac.finalize(), hmac::hmac("Jefe", ""));
+
+    assert_eq!(HmacSha512::default().finalize(), hmac::hmac("", ""));
+}
+
+#[test]
+fn verify() -> Result<(), Error> {
+    let mut hmac = HmacSha512::new("Jefe");
+    hmac.update("what do ya want for nothing?");
+    hmac.verify("164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737")?;
+
+    let result = hmac.verify(hmac::hmac("Jefe", "what do ya want for something?"));
+    assert!(matches!(result, Err(VerifyError::Mismatch { .. })));
+
+    Ok(())
+}
+
+#[test]
+fn file_and_directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_binary(b"what do ya want for nothing?")?;
+
+    let expected = hmac::hmac("Jefe", "what do ya want for nothing?");
+    assert_eq!(hmac::chksum("Jefe", file.path())?, expected);
+    assert_eq!(hmac::chksum("Jefe", temp_dir.path())?, expected);
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_file_and_directory() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"what do ya want for nothing?")?;
+
+        let expected = hmac::hmac("Jefe", "what do ya want for nothing?");
+        assert_eq!(hmac::async_chksum("Jefe", file.path()).await?, expected);
+        assert_eq!(hmac::async_chksum("Jefe", temp_dir.path()).await?, expected);
+    }
+
+    Ok(())
+}
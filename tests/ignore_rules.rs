@@ -0,0 +1,191 @@
+mod common;
+
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+#[cfg(feature = "ignore-rules")]
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild, PathCreateDir};
+#[cfg(feature = "ignore-rules")]
+use assert_fs::TempDir;
+#[cfg(all(feature = "ignore-rules", feature = "async-runtime-tokio"))]
+use chksum_sha2_512::async_chksum_dir;
+use chksum_sha2_512::DirError;
+#[cfg(feature = "ignore-rules")]
+use chksum_sha2_512::{chksum_dir, DirOptions, IgnoreRules};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Dir(#[from] DirError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[cfg_attr(not(feature = "ignore-rules"), ignore)]
+#[test]
+fn exclude_patterns() -> Result<(), Error> {
+    #[cfg(feature = "ignore-rules")]
+    {
+        let temp_dir = common::tree()?;
+        let clean = chksum_dir(temp_dir.path(), &DirOptions::new())?;
+
+        temp_dir.child("target/debug/build.o").write_binary(b"object")?;
+        temp_dir.child(".a.txt.swp").write_binary(b"swap")?;
+        assert_ne!(chksum_dir(temp_dir.path(), &DirOptions::new())?, clean);
+
+        let rules = IgnoreRules::new().exclude("target/").exclude("*.swp");
+        let options = DirOptions::new().with_rules(rules);
+        assert_eq!(chksum_dir(temp_dir.path(), &options)?, clean);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "ignore-rules"), ignore)]
+#[test]
+fn include_patterns() -> Result<(), Error> {
+    #[cfg(feature = "ignore-rules")]
+    {
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("a.txt").write_binary(b"example data")?;
+        let expected = chksum_dir(temp_dir.path(), &DirOptions::new())?;
+
+        temp_dir.child("sub/c.bin").write_binary(b"binary")?;
+        let options = DirOptions::new().with_rules(IgnoreRules::new().include("*.txt"));
+        assert_eq!(chksum_dir(temp_dir.path(), &options)?, expected);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "ignore-rules"), ignore)]
+#[test]
+fn ignore_files() -> Result<(), Error> {
+    #[cfg(feature = "ignore-rules")]
+    {
+        let temp_dir = common::tree()?;
+        temp_dir.child(".gitignore").write_binary(b"*.log\nbuild/\n")?;
+        temp_dir.child("sub/.ignore").write_binary(b"!keep.log\n")?;
+        let options = DirOptions::new().with_rules(IgnoreRules::new().respect_ignore_files(true));
+        let clean = chksum_dir(temp_dir.path(), &options)?;
+
+        temp_dir.child("debug.log").write_binary(b"log")?;
+        temp_dir.child("build/output").write_binary(b"output")?;
+        assert_eq!(chksum_dir(temp_dir.path(), &options)?, clean);
+        assert_ne!(chksum_dir(temp_dir.path(), &DirOptions::new())?, clean);
+
+        temp_dir.child("sub/keep.log").write_binary(b"log")?;
+        assert_ne!(chksum_dir(temp_dir.path(), &options)?, clean);
+
+        let rules = IgnoreRules::new().respect_ignore_files(true).exclude("keep.log");
+        let options = DirOptions::new().with_rules(rules);
+        assert_eq!(chksum_dir(temp_dir.path(), &options)?, clean);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "ignore-rules"), ignore)]
+#[test]
+fn ignored_entries_do_not_count_as_contents() -> Result<(), Error> {
+    #[cfg(feature = "ignore-rules")]
+    {
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("empty").create_dir_all()?;
+        let options = DirOptions::new().include_empty_dirs(true);
+        let expected = chksum_dir(temp_dir.path(), &options)?;
+
+        temp_dir.child("empty/file.tmp").touch()?;
+        let options = options.with_rules(IgnoreRules::new().exclude("*.tmp"));
+        assert_eq!(chksum_dir(temp_dir.path(), &options)?, expected);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "ignore-rules"), ignore)]
+#[test]
+fn files_not_included_do_not_count_as_contents() -> Result<(), Error> {
+    #[cfg(feature = "ignore-rules")]
+    {
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("a.txt").write_binary(b"example data")?;
+        temp_dir.child("docs").create_dir_all()?;
+        let options = DirOptions::new()
+            .include_empty_dirs(true)
+            .with_rules(IgnoreRules::new().include("*.txt"));
+        let expected = chksum_dir(temp_dir.path(), &options)?;
+
+        temp_dir.child("docs/readme.md").write_binary(b"readme")?;
+        assert_eq!(chksum_dir(temp_dir.path(), &options)?, expected);
+        #[cfg(feature = "async-runtime-tokio")]
+        {
+            let runtime = tokio::runtime::Runtime::new()?;
+            assert_eq!(runtime.block_on(async_chksum_dir(temp_dir.path(), &options))?, expected);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "ignore-rules"), ignore)]
+#[test]
+fn include_directory_patterns() -> Result<(), Error> {
+    #[cfg(feature = "ignore-rules")]
+    {
+        let temp_dir = common::tree()?;
+        let empty = chksum_dir(TempDir::new()?.path(), &DirOptions::new())?;
+        let options = DirOptions::new().with_rules(IgnoreRules::new().include("sub/"));
+        let digest = chksum_dir(temp_dir.path(), &options)?;
+        assert_ne!(digest, empty);
+
+        let expected = TempDir::new()?;
+        expected.child("sub").create_dir_all()?;
+        expected.child("sub/b.txt").touch()?;
+        assert_eq!(digest, chksum_dir(expected.path(), &DirOptions::new())?);
+
+        let options = DirOptions::new().with_rules(IgnoreRules::new().include("/sub"));
+        assert_eq!(chksum_dir(temp_dir.path(), &options)?, digest);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "ignore-rules"), ignore)]
+#[test]
+fn invalid_pattern() -> Result<(), Error> {
+    #[cfg(feature = "ignore-rules")]
+    {
+        let temp_dir = common::tree()?;
+        let options = DirOptions::new().with_rules(IgnoreRules::new().exclude("a[b"));
+        let result = chksum_dir(temp_dir.path(), &options);
+        assert!(matches!(result, Err(DirError::InvalidPattern { pattern, .. }) if pattern == "a[b"));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(all(feature = "ignore-rules", feature = "async-runtime-tokio")), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_same_as_sync() -> Result<(), Error> {
+    #[cfg(all(feature = "ignore-rules", feature = "async-runtime-tokio"))]
+    {
+        let temp_dir = common::tree()?;
+        temp_dir.child("empty").create_dir_all()?;
+        let options = DirOptions::new()
+            .include_sizes(true)
+            .include_modes(true)
+            .include_empty_dirs(true)
+            .with_rules(IgnoreRules::new().exclude("*.log").respect_ignore_files(true));
+        temp_dir.child("debug.log").write_binary(b"log")?;
+        temp_dir.child("sub/.gitignore").write_binary(b"b.txt\n")?;
+        assert_eq!(
+            async_chksum_dir(temp_dir.path(), &options).await?,
+            chksum_dir(temp_dir.path(), &options)?
+        );
+    }
+
+    Ok(())
+}
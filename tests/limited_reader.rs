@@ -0,0 +1,97 @@
+#[cfg(feature = "reader")]
+use std::io::{self, ErrorKind, Read};
+
+#[cfg(feature = "reader")]
+use chksum_sha2_512::reader::{self, LimitExceeded};
+#[cfg(feature = "reader")]
+use chksum_sha2_512::{hash, new};
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn within_limit() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "reader")]
+    {
+        let data = b"example data";
+        for limit in [12, 13, u64::MAX] {
+            let mut reader = reader::with_limit(&data[..], new(), limit);
+            let mut buffer = Vec::new();
+            reader.read_to_end(&mut buffer)?;
+            assert_eq!(buffer, data);
+            assert_eq!(reader.bytes_read(), 12);
+            assert_eq!(reader.limit(), limit);
+            assert_eq!(reader.digest(), hash(data));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn limit_exceeded() {
+    #[cfg(feature = "reader")]
+    {
+        let data = b"example data";
+
+        let mut reader = reader::with_limit(&data[..], new(), 7);
+        let mut buffer = Vec::new();
+        let error = reader.read_to_end(&mut buffer).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(LimitExceeded::from_io(&error).map(LimitExceeded::limit), Some(7));
+        assert_eq!(buffer, b"example");
+        assert_eq!(reader.bytes_read(), 7);
+        assert_eq!(reader.digest(), hash(b"example"));
+
+        let error = reader.read(&mut [0; 8]).unwrap_err();
+        assert!(LimitExceeded::from_io(&error).is_some());
+
+        let mut reader = reader::with_limit(&data[..], new(), 0);
+        let error = io::copy(&mut reader, &mut io::sink()).unwrap_err();
+        assert!(LimitExceeded::from_io(&error).is_some());
+        assert_eq!(reader.bytes_read(), 0);
+    }
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn endless_stream() {
+    #[cfg(feature = "reader")]
+    {
+        let mut reader = reader::with_limit(io::repeat(0), new(), 1_000_000);
+        let error = io::copy(&mut reader, &mut io::sink()).unwrap_err();
+        assert!(LimitExceeded::from_io(&error).is_some());
+        assert_eq!(reader.bytes_read(), 1_000_000);
+        assert_eq!(reader.digest(), hash(vec![0; 1_000_000]));
+    }
+}
+
+#[cfg_attr(not(all(feature = "reader", feature = "async-runtime-tokio")), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(all(feature = "reader", feature = "async-runtime-tokio"))]
+    {
+        use tokio::io::AsyncReadExt as _;
+
+        let data = b"example data";
+        let mut reader = reader::async_with_limit(&data[..], new(), 12);
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer).await?;
+        assert_eq!(buffer, data);
+        assert_eq!(reader.digest(), hash(data));
+
+        let mut reader = reader::async_with_limit(&data[..], new(), 7);
+        let mut buffer = Vec::new();
+        let error = reader.read_to_end(&mut buffer).await.unwrap_err();
+        assert_eq!(LimitExceeded::from_io(&error).map(LimitExceeded::limit), Some(7));
+        assert_eq!(buffer, b"example");
+        assert_eq!(reader.bytes_read(), 7);
+        assert_eq!(reader.digest(), hash(b"example"));
+
+        let mut reader = reader::async_with_limit(tokio::io::repeat(0), new(), 100_000);
+        let error = tokio::io::copy(&mut reader, &mut tokio::io::sink()).await.unwrap_err();
+        assert!(LimitExceeded::from_io(&error).is_some());
+        assert_eq!(reader.bytes_read(), 100_000);
+    }
+
+    Ok(())
+}
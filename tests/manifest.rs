@@ -0,0 +1,399 @@
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_512::manifest::{Entry, Format, Manifest, ManifestError, Mode, Status, VerifyOptions};
+use chksum_sha2_512::{hash, Error as ChksumError};
+
+const EMPTY: &str = "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e";
+const EXAMPLE: &str = "ed59c5759a9ece516cec0c0623142d0e9fe70a27d750eee7fd38f4550d50addd873d0fa1a51fc823c1e3d5cada203f4a05d8325caacb7d3e0727a701f3f07e5f";
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+}
+
+#[test]
+fn text_and_binary_mode() {
+    let mut manifest = Manifest::new();
+    manifest.push(Entry::new(hash(b""), "plain.txt", Mode::Text));
+    manifest.push(Entry::new(hash(b""), "plain.txt", Mode::Binary));
+
+    let expected = format!("{EMPTY}  plain.txt\n{EMPTY} *plain.txt\n");
+    assert_eq!(manifest.to_string(), expected);
+    assert_eq!(manifest.to_bytes(), expected.as_bytes());
+}
+
+#[test]
+fn escaping() -> Result<(), Error> {
+    let manifest: Manifest = [
+        Entry::new(hash(b"example data"), "a\\b", Mode::Text),
+        Entry::new(hash(b"example data"), "new\nline", Mode::Text),
+        Entry::new(hash(b"example data"), "carriage\rreturn", Mode::Binary),
+    ]
+    .into_iter()
+    .collect();
+
+    let expected = format!("\\{EXAMPLE}  a\\\\b\n\\{EXAMPLE}  new\\nline\n\\{EXAMPLE} *carriage\\rreturn\n");
+    assert_eq!(manifest.to_string(), expected);
+    assert_eq!(Manifest::parse(expected)?, manifest);
+
+    Ok(())
+}
+
+#[test]
+fn parse() -> Result<(), Error> {
+    let input = format!("{}  file name.txt\n{EXAMPLE} *dir/data\n", EMPTY.to_uppercase());
+    let manifest: Manifest = input.parse()?;
+    assert_eq!(manifest.len(), 2);
+
+    let entries = manifest.entries();
+    assert_eq!(entries[0].digest(), &hash(b""));
+    assert_eq!(entries[0].path().to_str(), Some("file name.txt"));
+    assert_eq!(entries[0].mode(), Mode::Text);
+    assert_eq!(entries[1].digest(), &hash(b"example data"));
+    assert_eq!(entries[1].path().to_str(), Some("dir/data"));
+    assert_eq!(entries[1].mode(), Mode::Binary);
+
+    let without_line_feed = format!("{EMPTY}  last");
+    assert_eq!(Manifest::parse(without_line_feed)?.len(), 1);
+    assert!(Manifest::parse("")?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn parse_malformed() {
+    let cases = [
+        format!("{EMPTY}  ok\n{}  short\n", &EMPTY[1..]),
+        format!("{EMPTY}  ok\n{EMPTY}\tx\n"),
+        format!("{EMPTY}  ok\n{EMPTY} xpath\n"),
+        format!("{EMPTY}  ok\n{EMPTY}  \n"),
+        format!("{EMPTY}  ok\n\\{EMPTY}  bad\\escape\n"),
+        format!("{EMPTY}  ok\n{}g  x\n", &EMPTY[1..]),
+        format!("{EMPTY}  ok\n \n"),
+    ];
+    for case in cases {
+        let result = Manifest::parse(&case);
+        assert!(matches!(result, Err(ManifestError::Malformed { line: 2 })), "{case:?}");
+    }
+}
+
+#[test]
+fn comments_and_blank_lines() -> Result<(), Error> {
+    let input = format!("# comment\n{EMPTY}  a.txt\n\n#{EXAMPLE}  b.txt\nSHA512 (c.txt) = {EXAMPLE}\n\n");
+    let manifest = Manifest::parse(&input)?;
+    let paths: Vec<_> = manifest
+        .entries()
+        .iter()
+        .map(|entry| entry.path().to_str().unwrap_or_default())
+        .collect();
+    assert_eq!(paths, ["a.txt", "c.txt"]);
+    assert!(manifest.malformed_lines().is_empty());
+
+    let lenient = Manifest::parse_lenient(format!("{input}bad\n"));
+    assert_eq!(lenient.entries(), manifest.entries());
+    assert_eq!(lenient.malformed_lines(), [7]);
+
+    let result = Manifest::parse(format!("{input}bad\n"));
+    assert!(matches!(result, Err(ManifestError::Malformed { line: 7 })));
+
+    Ok(())
+}
+
+#[test]
+fn crlf_line_endings() -> Result<(), Error> {
+    let input = format!("{EMPTY}  a.txt\r\nSHA512 (b.txt) = {EXAMPLE}\r\n\r\n# comment\r\n");
+    let manifest = Manifest::parse(&input)?;
+    let entries: Vec<_> = manifest
+        .entries()
+        .iter()
+        .map(|entry| (entry.path().to_str().unwrap_or_default(), entry.digest().clone()))
+        .collect();
+    assert_eq!(entries, [("a.txt", hash(b"")), ("b.txt", hash(b"example data"))]);
+
+    let lenient = Manifest::parse_lenient(&input);
+    assert_eq!(lenient.entries(), manifest.entries());
+    assert!(lenient.malformed_lines().is_empty());
+
+    let escaped = Manifest::parse(format!("\\{EMPTY}  new\\nline\r\n"))?;
+    assert_eq!(escaped.entries()[0].path().to_str(), Some("new\nline"));
+
+    Ok(())
+}
+
+#[test]
+fn bsd_format() -> Result<(), Error> {
+    let manifest: Manifest = [
+        Entry::new(hash(b""), "plain.txt", Mode::Binary),
+        Entry::new(hash(b"example data"), "a\\b", Mode::Binary),
+        Entry::new(hash(b"example data"), "new\nline", Mode::Binary),
+        Entry::new(hash(b"example data"), "with (parens) = x", Mode::Binary),
+    ]
+    .into_iter()
+    .collect();
+
+    let expected = format!(
+        "SHA512 (plain.txt) = {EMPTY}\n\\SHA512 (a\\\\b) = {EXAMPLE}\n\\SHA512 (new\\nline) = {EXAMPLE}\nSHA512 (with \
+         (parens) = x) = {EXAMPLE}\n"
+    );
+    let bytes = manifest.to_bytes_with_format(Format::Bsd);
+    assert_eq!(bytes, expected.as_bytes());
+    assert_eq!(Manifest::parse(&bytes)?, manifest);
+
+    let mut written = Vec::new();
+    manifest.write_with_format(&mut written, Format::Bsd)?;
+    assert_eq!(written, bytes);
+    assert_eq!(manifest.to_bytes_with_format(Format::Gnu), manifest.to_bytes());
+
+    Ok(())
+}
+
+#[test]
+fn mixed_formats() -> Result<(), Error> {
+    let input = format!(
+        "{EMPTY}  gnu\nSHA512 (bsd) = {}\n{EXAMPLE} *binary\n",
+        EXAMPLE.to_uppercase()
+    );
+    let manifest = Manifest::parse(input)?;
+
+    let entries: Vec<_> = manifest
+        .entries()
+        .iter()
+        .map(|entry| (entry.path().to_str().unwrap_or_default(), entry.mode()))
+        .collect();
+    assert_eq!(
+        entries,
+        [("gnu", Mode::Text), ("bsd", Mode::Binary), ("binary", Mode::Binary)]
+    );
+    assert_eq!(manifest.entries()[1].digest(), &hash(b"example data"));
+
+    Ok(())
+}
+
+#[test]
+fn bsd_format_long_path() -> Result<(), Error> {
+    let path = format!("{}  x", "a".repeat(120));
+    let manifest = Manifest::parse(format!("SHA512 ({path}) = {EXAMPLE}\n"))?;
+    assert_eq!(manifest.entries()[0].path().to_str(), Some(path.as_str()));
+    assert_eq!(manifest.entries()[0].digest(), &hash(b"example data"));
+
+    let manifest: Manifest = [Entry::new(hash(b""), path, Mode::Binary)].into_iter().collect();
+    let bytes = manifest.to_bytes_with_format(Format::Bsd);
+    assert_eq!(Manifest::parse(&bytes)?, manifest);
+
+    Ok(())
+}
+
+#[test]
+fn bsd_format_unsupported_algorithm() {
+    let input = format!("SHA512 (ok) = {EMPTY}\nSHA256 (file) = {}\n", &EMPTY[..64]);
+    let result = Manifest::parse(&input);
+    assert!(matches!(
+        result,
+        Err(ManifestError::UnsupportedAlgorithm { line: 2, algorithm }) if algorithm == "SHA256"
+    ));
+
+    let manifest = Manifest::parse_lenient(&input);
+    assert_eq!(manifest.len(), 1);
+    assert_eq!(manifest.malformed_lines(), [2]);
+}
+
+#[test]
+fn bsd_format_malformed() {
+    let cases = [
+        format!("SHA512 (file) {EMPTY}\n"),
+        format!("SHA512 () = {EMPTY}\n"),
+        format!("SHA512 (file) = {}\n", &EMPTY[1..]),
+        format!("SHA512(file) = {EMPTY}\n"),
+        format!("SHA 512 (file) = {EMPTY}\n"),
+    ];
+    for case in cases {
+        let result = Manifest::parse(&case);
+        assert!(matches!(result, Err(ManifestError::Malformed { line: 1 })), "{case:?}");
+    }
+}
+
+#[test]
+fn from_paths() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let first = temp_dir.child("first");
+    first.touch()?;
+    let second = temp_dir.child("second");
+    second.write_binary(b"example data")?;
+
+    let manifest = Manifest::from_paths([first.path(), second.path()], Mode::Binary)?;
+    let expected = format!(
+        "{EMPTY} *{}\n{EXAMPLE} *{}\n",
+        first.path().display(),
+        second.path().display()
+    );
+    assert_eq!(manifest.to_string(), expected);
+
+    let mut written = Vec::new();
+    manifest.write(&mut written)?;
+    assert_eq!(Manifest::read(written.as_slice())?, manifest);
+
+    let result = Manifest::from_paths([temp_dir.child("missing").path()], Mode::Text);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_path() -> Result<(), Error> {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = OsStr::from_bytes(b"invalid\xff");
+    let manifest: Manifest = [Entry::new(hash(b""), path, Mode::Text)].into_iter().collect();
+    let bytes = manifest.to_bytes();
+    assert!(bytes.ends_with(b"  invalid\xff\n"));
+    assert_eq!(Manifest::parse(bytes)?, manifest);
+
+    Ok(())
+}
+
+fn checkout() -> Result<(TempDir, Manifest), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("ok").write_binary(b"example data")?;
+    temp_dir.child("failed").write_binary(b"changed data")?;
+    temp_dir.child("dir/nested").touch()?;
+    let input = format!(
+        "{EXAMPLE}  ok\n{EXAMPLE} *failed\nnot a manifest line\n{EMPTY}  missing\n{EMPTY}  dir\n{EMPTY}  dir/nested\n"
+    );
+    let manifest = Manifest::parse_lenient(input);
+    Ok((temp_dir, manifest))
+}
+
+#[test]
+fn parse_lenient() -> Result<(), Error> {
+    let (_, manifest) = checkout()?;
+    assert_eq!(manifest.len(), 5);
+    assert_eq!(manifest.malformed_lines(), [3]);
+
+    let input = format!("{EMPTY}  ok\nbad\n");
+    assert!(matches!(
+        Manifest::parse(&input),
+        Err(ManifestError::Malformed { line: 2 })
+    ));
+    assert_eq!(Manifest::read_lenient(input.as_bytes())?.malformed_lines(), [2]);
+
+    Ok(())
+}
+
+#[test]
+fn verify() -> Result<(), Error> {
+    let (temp_dir, manifest) = checkout()?;
+    let report = manifest.verify(&VerifyOptions::new().with_base_dir(temp_dir.path()));
+
+    let statuses: Vec<_> = report
+        .entries()
+        .iter()
+        .map(|entry| (entry.path().to_str().unwrap_or_default(), entry.status()))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            ("ok", Status::Ok),
+            ("failed", Status::Failed),
+            ("missing", Status::Missing),
+            ("dir", report.entries()[3].status()),
+            ("dir/nested", Status::Ok),
+        ]
+    );
+    assert!(matches!(report.entries()[3].status(), Status::Unreadable { .. }));
+    assert_eq!(report.entries()[0].error(), None);
+    assert_eq!(report.entries()[1].error(), None);
+    assert!(report.entries()[2].error().is_some());
+    assert!(report.entries()[3].error().is_some());
+    assert_eq!(report.ok(), 2);
+    assert_eq!(report.failed(), 1);
+    assert_eq!(report.missing(), 1);
+    assert_eq!(report.unreadable(), 1);
+    assert_eq!(report.malformed(), 1);
+    assert_eq!(report.malformed_lines(), [3]);
+    assert!(!report.is_success());
+
+    assert_eq!(Status::Ok.to_string(), "OK");
+    assert_eq!(Status::Failed.to_string(), "FAILED");
+
+    Ok(())
+}
+
+#[test]
+fn verify_options() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("ok").write_binary(b"example data")?;
+    let options = VerifyOptions::new().with_base_dir(temp_dir.path());
+
+    let manifest = Manifest::parse_lenient(format!("{EXAMPLE}  ok\n"));
+    assert!(manifest.verify(&options).is_success());
+
+    let manifest = Manifest::parse_lenient(format!("{EXAMPLE}  ok\n{EMPTY}  missing\n"));
+    assert!(!manifest.verify(&options).is_success());
+    assert!(manifest.verify(&options.clone().with_ignore_missing(true)).is_success());
+
+    let manifest = Manifest::parse_lenient(format!("{EMPTY}  missing\n"));
+    assert!(!manifest.verify(&options.clone().with_ignore_missing(true)).is_success());
+
+    let manifest = Manifest::parse_lenient(format!("{EXAMPLE}  ok\nmalformed\n"));
+    assert!(manifest.verify(&options).is_success());
+    assert!(!manifest.verify(&options.clone().with_strict(true)).is_success());
+
+    assert!(!Manifest::new().verify(&options).is_success());
+
+    Ok(())
+}
+
+#[test]
+fn verify_without_base_dir() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file");
+    file.write_binary(b"example data")?;
+
+    let manifest = Manifest::from_paths([file.path()], Mode::Binary)?;
+    let report = manifest.verify(&VerifyOptions::new().with_base_dir("/nonexistent"));
+    assert_eq!(report.entries()[0].status(), Status::Ok);
+    assert!(manifest.verify(&VerifyOptions::new()).is_success());
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_from_paths() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file");
+        file.write_binary(b"example data")?;
+
+        let manifest = Manifest::async_from_paths([file.path()], Mode::Text).await?;
+        assert_eq!(manifest, Manifest::from_paths([file.path()], Mode::Text)?);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_verify() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let (temp_dir, manifest) = checkout()?;
+        let options = VerifyOptions::new().with_base_dir(temp_dir.path()).with_strict(true);
+        assert_eq!(manifest.async_verify(&options).await, manifest.verify(&options));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use std::fs::File;
+use std::io::{self, Error as IoError};
+use std::num::NonZeroUsize;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_512::merkle::{self, InclusionProof, MerkleHasher, ProofError};
+use chksum_sha2_512::{hash, Digest, SHA2_512};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Proof(#[from] ProofError),
+}
+
+fn leaf_length(length: usize) -> NonZeroUsize {
+    NonZeroUsize::new(length).unwrap()
+}
+
+/// Computes the root following the recursive definition of RFC 6962.
+fn reference_root(leaves: &[&[u8]]) -> Digest {
+    match leaves {
+        [] => hash(b""),
+        [leaf] => hash([&[0x00], *leaf].concat()),
+        leaves => {
+            let split = leaves.len().next_power_of_two() / 2;
+            let (left, right) = leaves.split_at(split);
+            let mut hash = SHA2_512::new();
+            hash.update([0x01]);
+            hash.update(reference_root(left));
+            hash.update(reference_root(right));
+            hash.digest()
+        },
+    }
+}
+
+#[test]
+fn root() -> Result<(), Error> {
+    let data: Vec<u8> = (0..17 * 4).map(|index| index as u8).collect();
+    for length in 0..=data.len() {
+        let data = &data[..length];
+        let tree = merkle::chksum(data, leaf_length(4))?;
+        let leaves: Vec<_> = data.chunks(4).collect();
+        assert_eq!(tree.root(), reference_root(&leaves));
+        assert_eq!(tree.leaves(), leaves.len() as u64);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn single_leaf_and_empty() -> Result<(), Error> {
+    let tree = merkle::chksum(&b"example data"[..], leaf_length(1024))?;
+    assert_eq!(tree.root(), merkle::leaf_hash(b"example data"));
+
+    let tree = merkle::chksum(io::empty(), leaf_length(1024))?;
+    assert_eq!(tree.leaves(), 0);
+    assert_eq!(tree.root(), hash(b""));
+    assert_eq!(tree.proof(0), None);
+
+    Ok(())
+}
+
+#[test]
+fn proofs() -> Result<(), Error> {
+    let data: Vec<u8> = (0..17 * 4).map(|index| index as u8).collect();
+    for count in 1..=17 {
+        let data = &data[..count * 4];
+        let tree = merkle::chksum(data, leaf_length(4))?;
+        let root = tree.root();
+        for (index, leaf) in data.chunks(4).enumerate() {
+            let proof = tree.proof(index as u64).unwrap();
+            assert_eq!(proof.index(), index as u64);
+            assert_eq!(proof.leaves(), count as u64);
+            proof.verify(leaf, &root)?;
+            assert_eq!(proof.verify(b"fake", &root), Err(ProofError::Mismatch));
+        }
+        assert_eq!(tree.proof(count as u64), None);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn malformed_proofs() -> Result<(), Error> {
+    let tree = merkle::chksum(&b"example data"[..], leaf_length(4))?;
+    let root = tree.root();
+    let proof = tree.proof(2).unwrap();
+    proof.verify(b"data", &root)?;
+
+    let invalid = InclusionProof::new(3, 3, proof.path().to_vec());
+    assert_eq!(
+        invalid.verify(b"data", &root),
+        Err(ProofError::InvalidIndex { index: 3, leaves: 3 })
+    );
+
+    let mut path = proof.path().to_vec();
+    path.push(root.clone());
+    let invalid = InclusionProof::new(2, 3, path);
+    assert_eq!(invalid.verify(b"data", &root), Err(ProofError::InvalidLength));
+
+    let invalid = InclusionProof::new(2, 3, Vec::new());
+    assert_eq!(invalid.verify(b"data", &root), Err(ProofError::InvalidLength));
+
+    let invalid = InclusionProof::new(2, 4, proof.path().to_vec());
+    assert!(invalid.verify(b"data", &root).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn hasher() -> Result<(), Error> {
+    let data: Vec<u8> = (0..100_000).map(|index| (index % 251) as u8).collect();
+    let mut hasher = MerkleHasher::new(leaf_length(1000));
+    for chunk in data.chunks(333) {
+        hasher.update(chunk);
+    }
+    let tree = hasher.finalize();
+    assert_eq!(tree, merkle::chksum(data.as_slice(), leaf_length(1000))?);
+    assert_eq!(tree.leaf_hashes()[5], merkle::leaf_hash(&data[5000..6000]));
+
+    let mut hasher = MerkleHasher::new(leaf_length(1000));
+    io::copy(&mut data.as_slice(), &mut hasher)?;
+    assert_eq!(hasher.finalize(), tree);
+
+    Ok(())
+}
+
+#[test]
+fn file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file");
+    let data: Vec<u8> = (0..100_000).map(|index| (index % 251) as u8).collect();
+    file.write_binary(&data)?;
+
+    let tree = merkle::chksum(File::open(file.path())?, leaf_length(4096))?;
+    assert_eq!(tree.root(), merkle::chksum(data.as_slice(), leaf_length(4096))?.root());
+    let proof = tree.proof(24).unwrap();
+    proof.verify(&data[24 * 4096..], &tree.root())?;
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_same_as_sync() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file");
+        let data: Vec<u8> = (0..100_000).map(|index| (index % 251) as u8).collect();
+        file.write_binary(&data)?;
+
+        let tree = merkle::async_chksum(tokio::fs::File::open(file.path()).await?, leaf_length(4096)).await?;
+        assert_eq!(tree, merkle::chksum(File::open(file.path())?, leaf_length(4096))?);
+    }
+
+    Ok(())
+}
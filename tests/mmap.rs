@@ -0,0 +1,73 @@
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+#[cfg(feature = "mmap")]
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild};
+#[cfg(feature = "mmap")]
+use assert_fs::TempDir;
+use chksum_sha2_512::Error as ChksumError;
+#[cfg(feature = "mmap")]
+use chksum_sha2_512::{chksum, chksum_mmap, MmapError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[cfg(feature = "mmap")]
+    #[error(transparent)]
+    Mmap(#[from] MmapError),
+}
+
+#[cfg_attr(not(feature = "mmap"), ignore)]
+#[test]
+fn same_as_chksum() -> Result<(), Error> {
+    #[cfg(feature = "mmap")]
+    {
+        let temp_dir = TempDir::new()?;
+
+        let file = temp_dir.child("empty");
+        file.touch()?;
+        assert_eq!(
+            unsafe { chksum_mmap(file.path())? }.to_hex_lowercase(),
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+        );
+
+        for length in [1, 111, 112, 128, 4096, 16 * 1024 * 1024 + 17] {
+            let data: Vec<u8> = (0..length).map(|index| (index % 251) as u8).collect();
+            file.write_binary(&data)?;
+            assert_eq!(unsafe { chksum_mmap(file.path())? }, chksum(file.path())?);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "mmap"), ignore)]
+#[test]
+fn missing_file() -> Result<(), Error> {
+    #[cfg(feature = "mmap")]
+    {
+        let temp_dir = TempDir::new()?;
+        let result = unsafe { chksum_mmap(temp_dir.child("missing").path()) };
+        assert!(matches!(result, Err(MmapError::Io(_))));
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[cfg_attr(not(feature = "mmap"), ignore)]
+#[test]
+fn special_file_fallback() -> Result<(), Error> {
+    #[cfg(feature = "mmap")]
+    {
+        let digest = unsafe { chksum_mmap("/dev/null")? };
+        assert_eq!(digest, chksum_sha2_512::hash(b""));
+    }
+
+    Ok(())
+}
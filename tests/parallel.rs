@@ -0,0 +1,82 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use assert_fs::fixture::FixtureError;
+#[cfg(feature = "parallel")]
+use assert_fs::prelude::{FileWriteBin, PathChild, PathCreateDir};
+#[cfg(feature = "parallel")]
+use assert_fs::TempDir;
+use chksum_sha2_512::DirError;
+#[cfg(feature = "parallel")]
+use chksum_sha2_512::{chksum_dir, par_chksum_dir, DirOptions};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Dir(#[from] DirError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+}
+
+#[cfg_attr(not(feature = "parallel"), ignore)]
+#[test]
+fn same_as_sequential() -> Result<(), Error> {
+    #[cfg(feature = "parallel")]
+    {
+        let temp_dir = TempDir::new()?;
+        for index in 0..64 {
+            let data = vec![index as u8; index * 1021];
+            temp_dir
+                .child(format!("dir{}/file{index}", index % 5))
+                .write_binary(&data)?;
+        }
+        temp_dir.child("empty").create_dir_all()?;
+
+        let options = DirOptions::new().include_sizes(true).include_empty_dirs(true);
+        let expected = chksum_dir(temp_dir.path(), &options)?;
+        for threads in [1, 2, 3, 8, 64, 128] {
+            let threads = NonZeroUsize::new(threads).unwrap();
+            assert_eq!(par_chksum_dir(temp_dir.path(), &options, threads)?, expected);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "parallel"), ignore)]
+#[test]
+fn empty_and_single_file() -> Result<(), Error> {
+    #[cfg(feature = "parallel")]
+    {
+        let temp_dir = TempDir::new()?;
+        let options = DirOptions::new();
+        let threads = NonZeroUsize::new(4).unwrap();
+        assert_eq!(
+            par_chksum_dir(temp_dir.path(), &options, threads)?,
+            chksum_dir(temp_dir.path(), &options)?
+        );
+
+        let file = temp_dir.child("file");
+        file.write_binary(b"example data")?;
+        assert_eq!(
+            par_chksum_dir(file.path(), &options, threads)?,
+            chksum_dir(file.path(), &options)?
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "parallel"), ignore)]
+#[test]
+fn missing_path() -> Result<(), Error> {
+    #[cfg(feature = "parallel")]
+    {
+        let temp_dir = TempDir::new()?;
+        let threads = NonZeroUsize::new(2).unwrap();
+        let result = par_chksum_dir(temp_dir.child("missing").path(), &DirOptions::new(), threads);
+        assert!(matches!(result, Err(DirError::Io(_))));
+    }
+
+    Ok(())
+}
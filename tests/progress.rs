@@ -0,0 +1,225 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+use chksum_sha2_512::progress::{self, Progress, ProgressReporter};
+use chksum_sha2_512::{
+    chksum,
+    chksum_dir,
+    chksum_dir_with_context,
+    hash,
+    DirContext,
+    DirError,
+    DirOptions,
+    Error as ChksumError,
+};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Dir(#[from] DirError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[cfg(any(feature = "reader", feature = "writer"))]
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+type Reports = Arc<Mutex<Vec<(u64, Option<u64>, Option<PathBuf>)>>>;
+
+fn recorder(interval: u64) -> (ProgressReporter, Reports) {
+    let reports = Reports::default();
+    let hook = {
+        let reports = Arc::clone(&reports);
+        move |progress: &Progress| {
+            let report = (
+                progress.processed(),
+                progress.total(),
+                progress.path().map(PathBuf::from),
+            );
+            reports.lock().unwrap().push(report);
+        }
+    };
+    (ProgressReporter::new(hook).with_interval(interval), reports)
+}
+
+fn processed(reports: &Reports) -> Vec<u64> {
+    reports
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(processed, ..)| *processed)
+        .collect()
+}
+
+#[test]
+fn default_reporter() {
+    let reporter = ProgressReporter::default();
+    assert_eq!(reporter.interval(), progress::DEFAULT_INTERVAL);
+    assert_eq!(reporter.total(), None);
+    assert_eq!(reporter.processed(), 0);
+}
+
+#[test]
+fn reports_at_interval() -> Result<(), Error> {
+    let data = vec![0; 4096];
+
+    let (reporter, reports) = recorder(1024);
+    let digest = progress::chksum(&data, reporter)?;
+    assert_eq!(digest, hash(&data));
+    assert_eq!(processed(&reports), [4096]);
+
+    let (reporter, reports) = recorder(0);
+    progress::chksum(b"", reporter)?;
+    assert_eq!(processed(&reports), [0]);
+
+    Ok(())
+}
+
+#[test]
+fn file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file");
+    let data: Vec<u8> = (0..100_000).map(|index| (index % 251) as u8).collect();
+    file.write_binary(&data)?;
+
+    let (reporter, reports) = recorder(10_000);
+    let digest = progress::chksum_file(file.path(), reporter)?;
+    assert_eq!(digest, chksum(file.path())?);
+
+    let reports = reports.lock().unwrap();
+    assert!(reports.len() > 1);
+    assert!(reports.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert!(reports
+        .iter()
+        .all(|(_, total, path)| { *total == Some(100_000) && path.as_deref() == Some(file.path()) }));
+    assert_eq!(reports.last().map(|(processed, ..)| *processed), Some(100_000));
+
+    Ok(())
+}
+
+#[test]
+fn file_with_total() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file");
+    file.write_binary(b"example data")?;
+
+    let (reporter, reports) = recorder(1);
+    progress::chksum_file(file.path(), reporter.with_total(42))?;
+    assert_eq!(
+        reports.lock().unwrap().last().and_then(|(_, total, _)| *total),
+        Some(42)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dir() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"example data")?;
+    temp_dir.child("sub").create_dir_all()?;
+    temp_dir.child("sub/b.txt").write_binary(&[0; 100])?;
+    temp_dir.child("sub/c.txt").touch()?;
+
+    let options = DirOptions::new().include_sizes(true);
+    let (reporter, reports) = recorder(1);
+    let digest = chksum_dir_with_context(temp_dir.path(), &options, DirContext::new().with_progress(reporter))?;
+    assert_eq!(digest, chksum_dir(temp_dir.path(), &options)?);
+
+    let reports = reports.lock().unwrap();
+    assert!(reports.iter().all(|(_, total, _)| *total == Some(112)));
+    let paths: Vec<_> = reports.iter().filter_map(|(_, _, path)| path.clone()).collect();
+    assert!(paths.contains(&temp_dir.child("a.txt").to_path_buf()));
+    assert!(paths.contains(&temp_dir.child("sub/b.txt").to_path_buf()));
+    assert_eq!(reports.last().map(|(processed, ..)| *processed), Some(112));
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn reader() -> Result<(), Error> {
+    #[cfg(feature = "reader")]
+    {
+        use std::io::Read;
+
+        use chksum_sha2_512::reader;
+
+        let data = vec![1; 4096];
+        let (reporter, reports) = recorder(1024);
+        let mut reader = reader::with_progress(data.as_slice(), reporter);
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        assert_eq!(reader.digest(), hash(&data));
+        assert_eq!(processed(&reports).last(), Some(&4096));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "writer"), ignore)]
+#[test]
+fn writer() -> Result<(), Error> {
+    #[cfg(feature = "writer")]
+    {
+        use std::io::Write;
+
+        use chksum_sha2_512::writer;
+
+        let data = vec![1; 4096];
+        let (reporter, reports) = recorder(1024);
+        let mut writer = writer::with_progress(Vec::new(), reporter);
+        for chunk in data.chunks(512) {
+            writer.write_all(chunk)?;
+        }
+        assert_eq!(writer.digest(), hash(&data));
+        assert_eq!(processed(&reports), [1024, 2048, 3072, 4096]);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_same_as_sync() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use chksum_sha2_512::async_chksum_dir_with_context;
+
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("a.txt").write_binary(b"example data")?;
+        temp_dir.child("sub").create_dir_all()?;
+        temp_dir.child("sub/b.txt").write_binary(&[0; 100])?;
+
+        let (reporter, reports) = recorder(1);
+        let digest = progress::async_chksum(temp_dir.child("a.txt").path(), reporter).await?;
+        assert_eq!(digest, chksum(temp_dir.child("a.txt").path())?);
+        assert_eq!(processed(&reports).last(), Some(&12));
+
+        let (reporter, reports) = recorder(1);
+        let digest = progress::async_chksum_file(temp_dir.child("sub/b.txt").path(), reporter).await?;
+        assert_eq!(digest, chksum(temp_dir.child("sub/b.txt").path())?);
+        assert_eq!(
+            reports
+                .lock()
+                .unwrap()
+                .last()
+                .map(|(processed, total, _)| (*processed, *total)),
+            Some((100, Some(100)))
+        );
+
+        let options = DirOptions::new();
+        let (reporter, reports) = recorder(1);
+        let context = DirContext::new().with_progress(reporter);
+        let digest = async_chksum_dir_with_context(temp_dir.path(), &options, context).await?;
+        assert_eq!(digest, chksum_dir(temp_dir.path(), &options)?);
+        assert_eq!(processed(&reports).last(), Some(&112));
+    }
+
+    Ok(())
+}
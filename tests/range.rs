@@ -0,0 +1,115 @@
+use std::fs::File;
+use std::io::{Cursor, Error as IoError};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_512::{chksum_range, hash, RangeError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Range(#[from] RangeError),
+}
+
+fn data() -> Vec<u8> {
+    (0..100_000).map(|index| (index % 251) as u8).collect()
+}
+
+#[test]
+fn file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file");
+    let data = data();
+    file.write_binary(&data)?;
+
+    for (offset, length) in [
+        (0, 0),
+        (0, 100_000),
+        (1, 111),
+        (50_000, 8192),
+        (99_999, 1),
+        (100_000, 0),
+    ] {
+        let start = offset as usize;
+        let end = start + length as usize;
+        assert_eq!(
+            chksum_range(File::open(file.path())?, offset, length)?,
+            hash(&data[start..end])
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn reused_file() -> Result<(), Error> {
+    let data = data();
+    let mut file = Cursor::new(&data);
+    assert_eq!(chksum_range(&mut file, 10, 20)?, hash(&data[10..30]));
+    assert_eq!(chksum_range(&mut file, 0, 10)?, hash(&data[..10]));
+
+    Ok(())
+}
+
+#[test]
+fn out_of_range() {
+    let data = data();
+
+    let result = chksum_range(Cursor::new(&data), 99_990, 11);
+    assert!(matches!(
+        result,
+        Err(RangeError::OutOfRange {
+            end: 100_001,
+            length: 100_000
+        })
+    ));
+
+    let result = chksum_range(Cursor::new(&data), 200_000, 1);
+    assert!(matches!(
+        result,
+        Err(RangeError::OutOfRange {
+            end: 200_001,
+            length: 100_000
+        })
+    ));
+
+    let result = chksum_range(Cursor::new(&data), 1, u64::MAX);
+    assert!(matches!(result, Err(RangeError::OutOfRange { end: u64::MAX, .. })));
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_same_as_sync() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use chksum_sha2_512::async_chksum_range;
+
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file");
+        let data = data();
+        file.write_binary(&data)?;
+
+        for (offset, length) in [(0, 0), (0, 100_000), (1, 111), (50_000, 8192)] {
+            assert_eq!(
+                async_chksum_range(tokio::fs::File::open(file.path()).await?, offset, length).await?,
+                chksum_range(File::open(file.path())?, offset, length)?
+            );
+        }
+
+        let result = async_chksum_range(tokio::fs::File::open(file.path()).await?, 99_990, 11).await;
+        assert!(matches!(
+            result,
+            Err(RangeError::OutOfRange {
+                end: 100_001,
+                length: 100_000
+            })
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+#[cfg(feature = "reader")]
+use std::io::{self, BufRead, BufReader, Cursor, ErrorKind, Read, Seek, SeekFrom};
+
+#[cfg(feature = "reader")]
+use chksum_sha2_512::hash;
+#[cfg(feature = "reader")]
+use chksum_sha2_512::reader::{self, BackwardSeek, SeekMode};
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn buffered_lines() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "reader")]
+    {
+        let data = b"first line\nsecond line\nthird line";
+        let mut reader = reader::buffered(BufReader::with_capacity(4, &data[..]));
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        assert_eq!(line, "first line\n");
+        assert_eq!(reader.digest(), hash(b"first line\n"));
+
+        let mut buffer = [0; 7];
+        reader.read_exact(&mut buffer)?;
+        assert_eq!(&buffer, b"second ");
+        assert_eq!(reader.digest(), hash(b"first line\nsecond "));
+
+        let lines = reader.by_ref().lines().collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(lines, ["line", "third line"]);
+        assert_eq!(reader.digest(), hash(data));
+
+        let mut reader = reader::buffered(&data[..]);
+        let length = reader.fill_buf()?.len();
+        assert_eq!(reader.digest(), hash(b""));
+        reader.consume(length);
+        assert_eq!(reader.digest(), hash(data));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "reader")]
+struct FailingRefill<'a> {
+    data: &'a [u8],
+    filled: bool,
+}
+
+#[cfg(feature = "reader")]
+impl Read for FailingRefill<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = self.fill_buf()?.len().min(buf.len());
+        buf[..length].copy_from_slice(&self.data[..length]);
+        self.consume(length);
+        Ok(length)
+    }
+}
+
+#[cfg(feature = "reader")]
+impl BufRead for FailingRefill<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.filled {
+            return Err(io::Error::other("refill failed"));
+        }
+        self.filled = true;
+        Ok(self.data)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.data = &self.data[amt..];
+        self.filled = false;
+    }
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn buffered_consume_error() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "reader")]
+    {
+        let data = b"example data";
+        let mut reader = reader::buffered(FailingRefill { data, filled: false });
+        let length = reader.fill_buf()?.len();
+        reader.consume(length);
+        assert_eq!(reader.digest(), hash(b""));
+        let error = reader.fill_buf().unwrap_err();
+        assert_eq!(error.to_string(), "refill failed");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn seek_forward() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "reader")]
+    {
+        let data = b"example data";
+        for mode in [SeekMode::Reject, SeekMode::Rehash] {
+            let mut reader = reader::seekable(Cursor::new(data), mode);
+            assert_eq!(reader.seek(SeekFrom::Start(3))?, 3);
+            assert_eq!(reader.seek(SeekFrom::Current(2))?, 5);
+            assert_eq!(reader.stream_position()?, 5);
+            assert_eq!(reader.digest(), hash(b"examp"));
+            assert_eq!(reader.seek(SeekFrom::End(-4))?, 8);
+            assert_eq!(reader.digest(), hash(b"example "));
+            let mut buffer = Vec::new();
+            reader.read_to_end(&mut buffer)?;
+            assert_eq!(buffer, b"data");
+            assert_eq!(reader.position(), 12);
+            assert_eq!(reader.digest(), hash(data));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn seek_backward() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "reader")]
+    {
+        let data = b"example data";
+
+        let mut reader = reader::seekable(Cursor::new(data), SeekMode::Reject);
+        reader.seek(SeekFrom::Start(8))?;
+        let error = reader.seek(SeekFrom::Current(-1)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        let backward = BackwardSeek::from_io(&error).unwrap();
+        assert_eq!((backward.position(), backward.target()), (8, 7));
+        assert_eq!(reader.position(), 8);
+        assert_eq!(reader.digest(), hash(b"example "));
+
+        let mut reader = reader::seekable(Cursor::new(data), SeekMode::Rehash);
+        io::copy(&mut reader, &mut io::sink())?;
+        assert_eq!(reader.seek(SeekFrom::Start(7))?, 7);
+        assert_eq!(reader.digest(), hash(b"example"));
+        assert_eq!(reader.seek(SeekFrom::Start(0))?, 0);
+        assert_eq!(reader.digest(), hash(b""));
+        io::copy(&mut reader, &mut io::sink())?;
+        assert_eq!(reader.digest(), hash(data));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn seek_invalid() {
+    #[cfg(feature = "reader")]
+    {
+        let data = b"example data";
+
+        let mut reader = reader::seekable(Cursor::new(data), SeekMode::Rehash);
+        let error = reader.seek(SeekFrom::Start(13)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(reader.position(), 12);
+        assert_eq!(reader.digest(), hash(data));
+
+        let error = reader.seek(SeekFrom::Current(-13)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert!(BackwardSeek::from_io(&error).is_none());
+    }
+}
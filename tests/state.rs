@@ -0,0 +1,69 @@
+use chksum_sha2_512::{StateError, SHA2_512};
+
+#[test]
+fn export_and_import() -> Result<(), StateError> {
+    let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+    for split in [0, 1, 127, 128, 129, 256, 999, 1000] {
+        let (head, tail) = data.split_at(split);
+
+        let mut hash = SHA2_512::new();
+        hash.update(head);
+        let state = hash.export_state();
+        assert_eq!(state.len(), 90 + split % 128);
+
+        let mut hash = SHA2_512::import_state(&state)?;
+        hash.update(tail);
+        assert_eq!(hash.digest(), SHA2_512::hash(&data), "split at {split}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn import_resets_to_initial_state() -> Result<(), StateError> {
+    let mut hash = SHA2_512::new();
+    hash.update(b"example data");
+
+    let mut hash = SHA2_512::import_state(&hash.export_state())?;
+    hash.reset();
+    assert_eq!(hash, SHA2_512::new());
+
+    Ok(())
+}
+
+#[test]
+fn import_invalid_state() {
+    let mut hash = SHA2_512::new();
+    hash.update([0u8; 130]);
+    let state = hash.export_state();
+
+    let result = SHA2_512::import_state(&state[..50]);
+    assert_eq!(result.err(), Some(StateError::Truncated { value: 50, minimum: 90 }));
+
+    let mut invalid = state.clone();
+    invalid[0] = b'X';
+    assert_eq!(SHA2_512::import_state(&invalid).err(), Some(StateError::InvalidMagic));
+
+    let mut invalid = state.clone();
+    invalid[8] = 2;
+    let expected = StateError::UnsupportedVersion { version: 2 };
+    assert_eq!(SHA2_512::import_state(&invalid).err(), Some(expected));
+
+    let mut invalid = state.clone();
+    invalid[88] |= 1;
+    let expected = StateError::InvalidProcessedLength { value: 129 };
+    assert_eq!(SHA2_512::import_state(&invalid).err(), Some(expected));
+
+    let mut invalid = state.clone();
+    invalid[89] = 128;
+    let expected = StateError::InvalidBufferLength { value: 128 };
+    assert_eq!(SHA2_512::import_state(&invalid).err(), Some(expected));
+
+    let expected = StateError::InvalidLength { value: 1, proper: 2 };
+    assert_eq!(SHA2_512::import_state(&state[..91]).err(), Some(expected));
+
+    let mut invalid = state;
+    invalid.push(0);
+    let expected = StateError::InvalidLength { value: 3, proper: 2 };
+    assert_eq!(SHA2_512::import_state(&invalid).err(), Some(expected));
+}
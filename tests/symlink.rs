@@ -0,0 +1,173 @@
+#![cfg(unix)]
+
+mod common;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild, SymlinkToDir, SymlinkToFile};
+#[cfg(feature = "async-runtime-tokio")]
+use chksum_sha2_512::async_chksum_dir;
+use chksum_sha2_512::{chksum_dir, DirError, DirOptions, SymlinkPolicy};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Dir(#[from] DirError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+}
+
+fn options(policy: SymlinkPolicy) -> DirOptions {
+    DirOptions::new().with_symlink_policy(policy)
+}
+
+#[test]
+fn default_policy_is_follow() {
+    assert_eq!(DirOptions::new().symlink_policy(), SymlinkPolicy::Follow);
+    assert_eq!(SymlinkPolicy::default(), SymlinkPolicy::Follow);
+}
+
+#[test]
+fn follow() -> Result<(), Error> {
+    let linked = common::tree()?;
+    linked.child("link.txt").symlink_to_file(linked.child("a.txt").path())?;
+    linked.child("link").symlink_to_dir(linked.child("sub").path())?;
+
+    let copied = common::tree()?;
+    copied.child("link.txt").write_binary(b"example data")?;
+    copied.child("link/b.txt").touch()?;
+
+    let options = options(SymlinkPolicy::Follow);
+    assert_eq!(
+        chksum_dir(linked.path(), &options)?,
+        chksum_dir(copied.path(), &options)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn follow_root() -> Result<(), Error> {
+    let temp_dir = common::tree()?;
+    temp_dir.child("root").symlink_to_dir(temp_dir.child("sub").path())?;
+
+    let options = options(SymlinkPolicy::Follow);
+    assert_eq!(
+        chksum_dir(temp_dir.child("root").path(), &options)?,
+        chksum_dir(temp_dir.child("sub").path(), &options)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn hash_target() -> Result<(), Error> {
+    let temp_dir = common::tree()?;
+    temp_dir.child("link").symlink_to_file("a.txt")?;
+
+    let options = options(SymlinkPolicy::HashTarget);
+    let digest = chksum_dir(temp_dir.path(), &options)?;
+    assert_ne!(
+        digest,
+        chksum_dir(temp_dir.path(), &self::options(SymlinkPolicy::Follow))?
+    );
+
+    let other = common::tree()?;
+    other.child("link").symlink_to_file("sub/b.txt")?;
+    assert_ne!(chksum_dir(other.path(), &options)?, digest);
+
+    Ok(())
+}
+
+#[test]
+fn skip() -> Result<(), Error> {
+    let linked = common::tree()?;
+    linked.child("link.txt").symlink_to_file(linked.child("a.txt").path())?;
+    linked.child("dangling").symlink_to_file("missing")?;
+
+    let plain = common::tree()?;
+
+    let options = options(SymlinkPolicy::Skip);
+    assert_eq!(
+        chksum_dir(linked.path(), &options)?,
+        chksum_dir(plain.path(), &options)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn error() -> Result<(), Error> {
+    let temp_dir = common::tree()?;
+    assert!(chksum_dir(temp_dir.path(), &options(SymlinkPolicy::Error)).is_ok());
+
+    temp_dir.child("sub/link").symlink_to_file("b.txt")?;
+    let result = chksum_dir(temp_dir.path(), &options(SymlinkPolicy::Error));
+    assert!(matches!(result, Err(DirError::Symlink { path }) if path == temp_dir.child("sub/link").path()));
+
+    Ok(())
+}
+
+#[test]
+fn dangling() -> Result<(), Error> {
+    let temp_dir = common::tree()?;
+    temp_dir.child("dangling").symlink_to_file("missing")?;
+
+    let result = chksum_dir(temp_dir.path(), &options(SymlinkPolicy::Follow));
+    assert!(matches!(result, Err(DirError::DanglingSymlink { path }) if path == temp_dir.child("dangling").path()));
+    assert!(chksum_dir(temp_dir.path(), &options(SymlinkPolicy::HashTarget)).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn cycle() -> Result<(), Error> {
+    let temp_dir = common::tree()?;
+    temp_dir.child("sub/loop").symlink_to_dir(temp_dir.path())?;
+
+    let result = chksum_dir(temp_dir.path(), &options(SymlinkPolicy::Follow));
+    assert!(matches!(result, Err(DirError::SymlinkCycle { path }) if path == temp_dir.child("sub/loop").path()));
+    assert!(chksum_dir(temp_dir.path(), &options(SymlinkPolicy::HashTarget)).is_ok());
+    assert_eq!(
+        chksum_dir(temp_dir.path(), &options(SymlinkPolicy::Skip))?,
+        chksum_dir(common::tree()?.path(), &options(SymlinkPolicy::Skip))?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn repeated_link_is_not_cycle() -> Result<(), Error> {
+    let temp_dir = common::tree()?;
+    temp_dir.child("first").symlink_to_dir(temp_dir.child("sub").path())?;
+    temp_dir.child("second").symlink_to_dir(temp_dir.child("sub").path())?;
+
+    assert!(chksum_dir(temp_dir.path(), &options(SymlinkPolicy::Follow)).is_ok());
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_same_as_sync() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = common::tree()?;
+        temp_dir.child("link.txt").symlink_to_file("a.txt")?;
+        temp_dir.child("link").symlink_to_dir(temp_dir.child("sub").path())?;
+        for policy in [SymlinkPolicy::Follow, SymlinkPolicy::HashTarget, SymlinkPolicy::Skip] {
+            let options = options(policy);
+            assert_eq!(
+                async_chksum_dir(temp_dir.path(), &options).await?,
+                chksum_dir(temp_dir.path(), &options)?
+            );
+        }
+
+        temp_dir.child("sub/loop").symlink_to_dir(temp_dir.path())?;
+        let result = async_chksum_dir(temp_dir.path(), &options(SymlinkPolicy::Follow)).await;
+        assert!(matches!(result, Err(DirError::SymlinkCycle { .. })));
+        let result = async_chksum_dir(temp_dir.path(), &options(SymlinkPolicy::Error)).await;
+        assert!(matches!(result, Err(DirError::Symlink { .. })));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+#[cfg(feature = "reader")]
+use std::io::{self, ErrorKind, Read, Write};
+
+#[cfg(feature = "reader")]
+use chksum_sha2_512::hash;
+#[cfg(feature = "reader")]
+use chksum_sha2_512::reader;
+
+#[cfg(feature = "reader")]
+struct FailingSink;
+
+#[cfg(feature = "reader")]
+impl Write for FailingSink {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(ErrorKind::BrokenPipe, "sink closed"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn forwards_and_hashes() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "reader")]
+    {
+        let data: Vec<u8> = (0..100_000).map(|index| (index % 251) as u8).collect();
+        let mut reader = reader::tee(data.as_slice(), Vec::new());
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        assert_eq!(buffer, data);
+        assert_eq!(reader.digest(), hash(&data));
+        let (digest, sink) = reader.finish()?;
+        assert_eq!(digest, hash(&data));
+        assert_eq!(sink, data);
+
+        let reader = reader::tee(io::empty(), Vec::new());
+        let (digest, sink) = reader.finish()?;
+        assert_eq!(digest, hash(b""));
+        assert!(sink.is_empty());
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn sink_error() {
+    #[cfg(feature = "reader")]
+    {
+        let mut reader = reader::tee(&b"example data"[..], FailingSink);
+        let error = io::copy(&mut reader, &mut io::sink()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+        assert_eq!(reader.digest(), hash(b""));
+    }
+}
+
+#[cfg_attr(not(all(feature = "reader", feature = "async-runtime-tokio")), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(all(feature = "reader", feature = "async-runtime-tokio"))]
+    {
+        use tokio::io::AsyncReadExt as _;
+
+        let data: Vec<u8> = (0..100_000).map(|index| (index % 251) as u8).collect();
+        let mut reader = reader::async_tee(data.as_slice(), Vec::new());
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer).await?;
+        assert_eq!(buffer, data);
+        let (digest, sink) = reader.finish().await?;
+        assert_eq!(digest, hash(&data));
+        assert_eq!(sink, data);
+
+        let (sink, mut drain) = tokio::io::duplex(16);
+        let mut reader = reader::async_tee(&b"example data"[..], sink);
+        let mut buffer = [0; 8];
+        let length = reader.read(&mut buffer).await?;
+        assert_eq!(&buffer[..length], &b"example data"[..length]);
+        tokio::io::copy(&mut reader, &mut tokio::io::sink()).await?;
+        let (digest, sink) = reader.finish().await?;
+        drop(sink);
+        let mut forwarded = Vec::new();
+        drain.read_to_end(&mut forwarded).await?;
+        assert_eq!(forwarded, b"example data");
+        assert_eq!(digest, hash(b"example data"));
+    }
+
+    Ok(())
+}
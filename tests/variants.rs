@@ -0,0 +1,237 @@
+use std::io::Error as IoError;
+#[cfg(feature = "reader")]
+use std::io::Read;
+#[cfg(feature = "writer")]
+use std::io::Write;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha2_512::{sha2_384, sha2_512_224, sha2_512_256, sha2_512_t, Error as ChksumError};
+
+const ONE_BLOCK: &str = "abc";
+const TWO_BLOCKS: &str =
+    "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+// Test vectors from the NIST examples for FIPS 180-4.
+#[test]
+fn sha2_512_vectors() {
+    let digest = chksum_sha2_512::hash(ONE_BLOCK).to_hex_lowercase();
+    assert_eq!(digest, "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f");
+
+    let digest = chksum_sha2_512::hash(TWO_BLOCKS).to_hex_lowercase();
+    assert_eq!(digest, "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909");
+
+    let digest = chksum_sha2_512::hash(vec![b'a'; 1_000_000]).to_hex_lowercase();
+    assert_eq!(digest, "e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973ebde0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b");
+}
+
+#[test]
+fn sha2_384_vectors() {
+    let digest = sha2_384::hash("").to_hex_lowercase();
+    assert_eq!(
+        digest,
+        "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b"
+    );
+
+    let digest = sha2_384::hash(ONE_BLOCK).to_hex_lowercase();
+    assert_eq!(
+        digest,
+        "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7"
+    );
+
+    let digest = sha2_384::hash(TWO_BLOCKS).to_hex_lowercase();
+    assert_eq!(
+        digest,
+        "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712fcc7c71a557e2db966c3e9fa91746039"
+    );
+
+    let digest = sha2_384::hash(vec![b'a'; 1_000_000]).to_hex_lowercase();
+    assert_eq!(
+        digest,
+        "9d0e1809716474cb086e834e310a4a1ced149e9c00f248527972cec5704c2a5b07b8b3dc38ecc4ebae97ddd87f3d8985"
+    );
+}
+
+#[test]
+fn sha2_512_224_vectors() {
+    let digest = sha2_512_224::hash("").to_hex_lowercase();
+    assert_eq!(digest, "6ed0dd02806fa89e25de060c19d3ac86cabb87d6a0ddd05c333b84f4");
+
+    let digest = sha2_512_224::hash(ONE_BLOCK).to_hex_lowercase();
+    assert_eq!(digest, "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa");
+
+    let digest = sha2_512_224::hash(TWO_BLOCKS).to_hex_lowercase();
+    assert_eq!(digest, "23fec5bb94d60b23308192640b0c453335d664734fe40e7268674af9");
+
+    let digest = sha2_512_224::hash(vec![b'a'; 1_000_000]).to_hex_lowercase();
+    assert_eq!(digest, "37ab331d76f0d36de422bd0edeb22a28accd487b7a8453ae965dd287");
+}
+
+#[test]
+fn sha2_512_256_vectors() {
+    let digest = sha2_512_256::hash("").to_hex_lowercase();
+    assert_eq!(
+        digest,
+        "c672b8d1ef56ed28ab87c3622c5114069bdd3ad7b8f9737498d0c01ecef0967a"
+    );
+
+    let digest = sha2_512_256::hash(ONE_BLOCK).to_hex_lowercase();
+    assert_eq!(
+        digest,
+        "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"
+    );
+
+    let digest = sha2_512_256::hash(TWO_BLOCKS).to_hex_lowercase();
+    assert_eq!(
+        digest,
+        "3928e184fb8690f840da3988121d31be65cb9d3ef83ee6146feac861e19b563a"
+    );
+
+    let digest = sha2_512_256::hash(vec![b'a'; 1_000_000]).to_hex_lowercase();
+    assert_eq!(
+        digest,
+        "9a59a052930187a97038cae692f30708aa6491923ef5194394dc68d56c74fb21"
+    );
+}
+
+#[test]
+fn incremental_update_across_block_boundaries() {
+    let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+    for split in [0, 1, 111, 112, 127, 128, 129, 255, 256, 999, 1000] {
+        let (head, tail) = data.split_at(split);
+
+        let mut hash = sha2_384::new();
+        hash.update(head);
+        hash.update(tail);
+        assert_eq!(hash.digest(), sha2_384::hash(&data), "split at {split}");
+
+        let mut hash = chksum_sha2_512::new();
+        hash.update(head);
+        hash.update(tail);
+        assert_eq!(hash.digest(), chksum_sha2_512::hash(&data), "split at {split}");
+    }
+}
+
+#[test]
+fn reset() {
+    let mut hash = sha2_512_256::new();
+    hash.update(ONE_BLOCK);
+    hash.reset();
+    assert_eq!(hash.digest(), sha2_512_256::hash(""));
+}
+
+#[test]
+fn sha2_512_t_initial_hash_values() {
+    let iv = sha2_512_t::initial_hash_value(224).expect("224 is a valid length");
+    let digest = {
+        let mut hash = sha2_512_224::new();
+        hash.update(ONE_BLOCK);
+        hash.digest()
+    };
+    assert_eq!(iv[0], 0x8C3D_37C8_1954_4DA2);
+    assert_eq!(iv[7], 0x1112_E6AD_91D6_92A1);
+    assert_eq!(digest, sha2_512_224::hash(ONE_BLOCK));
+
+    let iv = sha2_512_t::initial_hash_value(256).expect("256 is a valid length");
+    assert_eq!(
+        iv,
+        [
+            0x2231_2194_FC2B_F72C,
+            0x9F55_5FA3_C84C_64C2,
+            0x2393_B86B_6F53_B151,
+            0x9638_7719_5940_EABD,
+            0x9628_3EE2_A88E_FFE3,
+            0xBE5E_1E25_5386_3992,
+            0x2B01_99FC_2C85_B8AA,
+            0x0EB7_2DDC_81C5_2CA2,
+        ]
+    );
+
+    assert!(sha2_512_t::initial_hash_value(0).is_none());
+    assert!(sha2_512_t::initial_hash_value(384).is_none());
+    assert!(sha2_512_t::initial_hash_value(512).is_none());
+}
+
+#[test]
+fn digest_from_hex() {
+    let digest = sha2_512_224::hash(ONE_BLOCK);
+    let parsed: sha2_512_224::Digest = "4634270F707B6A54DAAE7530460842E20E37ED265CEEE9A43E8924AA"
+        .parse()
+        .expect("digest must be valid");
+    assert_eq!(parsed, digest);
+    assert!(sha2_384::Digest::from_hex(&digest.to_hex_lowercase()).is_err());
+}
+
+#[test]
+fn file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_binary(ONE_BLOCK.as_bytes())?;
+
+    assert_eq!(sha2_384::chksum(file.path())?, sha2_384::hash(ONE_BLOCK));
+    assert_eq!(sha2_512_224::chksum(file.path())?, sha2_512_224::hash(ONE_BLOCK));
+    assert_eq!(sha2_512_256::chksum(temp_dir.path())?, sha2_512_256::hash(ONE_BLOCK));
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_file() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.txt");
+        file.write_binary(ONE_BLOCK.as_bytes())?;
+
+        assert_eq!(sha2_384::async_chksum(file.path()).await?, sha2_384::hash(ONE_BLOCK));
+        assert_eq!(
+            sha2_512_224::async_chksum(file.path()).await?,
+            sha2_512_224::hash(ONE_BLOCK)
+        );
+        assert_eq!(
+            sha2_512_256::async_chksum(temp_dir.path()).await?,
+            sha2_512_256::hash(ONE_BLOCK)
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn reader() -> Result<(), Error> {
+    #[cfg(feature = "reader")]
+    {
+        let mut reader = sha2_384::Reader::new(ONE_BLOCK.as_bytes());
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        assert_eq!(reader.digest(), sha2_384::hash(ONE_BLOCK));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "writer"), ignore)]
+#[test]
+fn writer() -> Result<(), Error> {
+    #[cfg(feature = "writer")]
+    {
+        let mut writer = sha2_512_256::Writer::new(Vec::new());
+        writer.write_all(ONE_BLOCK.as_bytes())?;
+        assert_eq!(writer.digest(), sha2_512_256::hash(ONE_BLOCK));
+    }
+
+    Ok(())
+}
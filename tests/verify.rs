@@ -0,0 +1,88 @@
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+#[cfg(feature = "async-runtime-tokio")]
+use chksum_sha2_512::async_verify;
+use chksum_sha2_512::{hash, verify, FormatError, VerifyError};
+
+const DATA_DIGEST: &str = "ed59c5759a9ece516cec0c0623142d0e9fe70a27d750eee7fd38f4550d50addd873d0fa1a51fc823c1e3d5cada203f4a05d8325caacb7d3e0727a701f3f07e5f";
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Verify(#[from] VerifyError),
+}
+
+#[test]
+fn verify_bytes() -> Result<(), Error> {
+    let data = b"example data";
+    verify(data, DATA_DIGEST)?;
+    verify(data, DATA_DIGEST.to_uppercase())?;
+    verify(data, hash(data))?;
+    verify(data, hash(data).into_inner())?;
+    verify(data, hash(data).as_bytes())?;
+
+    Ok(())
+}
+
+#[test]
+fn verify_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_binary(b"example data")?;
+
+    verify(file.path(), DATA_DIGEST)?;
+
+    Ok(())
+}
+
+#[test]
+fn verify_mismatch() {
+    let result = verify(b"other data", DATA_DIGEST);
+    match result {
+        Err(VerifyError::Mismatch { expected, actual }) => {
+            assert_eq!(*expected, DATA_DIGEST);
+            assert_eq!(*actual, hash(b"other data"));
+        },
+        _ => panic!("expected mismatch, got {result:?}"),
+    }
+}
+
+#[test]
+fn verify_malformed() {
+    let result = verify(b"example data", &DATA_DIGEST[1..]);
+    assert!(matches!(
+        result,
+        Err(VerifyError::Format(FormatError::InvalidLength { value: 127, .. }))
+    ));
+
+    let result = verify(b"example data", &[0u8; 32][..]);
+    assert!(matches!(
+        result,
+        Err(VerifyError::Format(FormatError::InvalidLength { value: 32, .. }))
+    ));
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_verify_file() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"example data")?;
+
+        async_verify(file.path(), DATA_DIGEST).await?;
+
+        let result = async_verify(file.path(), hash(b"other data")).await;
+        assert!(matches!(result, Err(VerifyError::Mismatch { .. })));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+#[cfg(feature = "reader")]
+use std::io::{self, ErrorKind, Read};
+
+#[cfg(feature = "reader")]
+use chksum_sha2_512::hash;
+#[cfg(feature = "reader")]
+use chksum_sha2_512::reader::{VerificationError, VerifyingReader};
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn matching() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "reader")]
+    {
+        let data = b"example data";
+        let mut reader = VerifyingReader::new(&data[..], hash(data));
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        assert_eq!(buffer, data);
+        assert_eq!(reader.read(&mut [0; 8])?, 0);
+
+        let mut reader = VerifyingReader::new(&data[..], hash(data)).with_length(12);
+        assert_eq!(io::copy(&mut reader, &mut io::sink())?, 12);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn digest_mismatch() {
+    #[cfg(feature = "reader")]
+    {
+        let mut reader = VerifyingReader::new(&b"tampered data"[..], hash(b"example data"));
+        let mut buffer = Vec::new();
+        let error = reader.read_to_end(&mut buffer).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(buffer, b"tampered data");
+        assert!(matches!(
+            VerificationError::from_io(&error),
+            Some(VerificationError::Mismatch { expected, actual })
+                if **expected == hash(b"example data") && **actual == hash(b"tampered data")
+        ));
+
+        let error = reader.read(&mut [0; 8]).unwrap_err();
+        assert!(VerificationError::from_io(&error).is_some());
+    }
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn length_mismatch() {
+    #[cfg(feature = "reader")]
+    {
+        let data = b"example data";
+
+        let mut reader = VerifyingReader::new(&data[..], hash(data)).with_length(13);
+        let error = io::copy(&mut reader, &mut io::sink()).unwrap_err();
+        assert!(matches!(
+            VerificationError::from_io(&error),
+            Some(VerificationError::LengthMismatch {
+                expected: 13,
+                actual: 12
+            })
+        ));
+
+        let mut reader = VerifyingReader::new(&data[..], hash(&data[..4])).with_length(4);
+        let mut buffer = [0; 4];
+        reader.read_exact(&mut buffer).unwrap();
+        let error = reader.read(&mut [0; 4]).unwrap_err();
+        assert!(matches!(
+            VerificationError::from_io(&error),
+            Some(VerificationError::LengthMismatch { expected: 4, actual: 8 })
+        ));
+    }
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn other_errors_are_not_verification_errors() {
+    #[cfg(feature = "reader")]
+    {
+        let error = io::Error::new(ErrorKind::InvalidData, "other");
+        assert!(VerificationError::from_io(&error).is_none());
+    }
+}
+
+#[cfg_attr(not(all(feature = "reader", feature = "async-runtime-tokio")), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(all(feature = "reader", feature = "async-runtime-tokio"))]
+    {
+        use chksum_sha2_512::reader::AsyncVerifyingReader;
+        use tokio::io::AsyncReadExt as _;
+
+        let data = b"example data";
+        let mut reader = AsyncVerifyingReader::new(&data[..], hash(data)).with_length(12);
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer).await?;
+        assert_eq!(buffer, data);
+
+        let mut reader = AsyncVerifyingReader::new(&b"tampered data"[..], hash(data));
+        let error = reader.read_to_end(&mut Vec::new()).await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(matches!(
+            VerificationError::from_io(&error),
+            Some(VerificationError::Mismatch { .. })
+        ));
+
+        let mut reader = AsyncVerifyingReader::new(&data[..], hash(data)).with_length(4);
+        let error = reader.read_to_end(&mut Vec::new()).await.unwrap_err();
+        assert!(matches!(
+            VerificationError::from_io(&error),
+            Some(VerificationError::LengthMismatch { expected: 4, .. })
+        ));
+    }
+
+    Ok(())
+}